@@ -1,24 +1,44 @@
 /*
 cargo run -p apple-app-store-connect-api-token-cli --bin apple_app_store_connect_api_token_gen -- 'key_id' '/path/AuthKey_xxx.p8' 'issuer_id'
+cargo run -p apple-app-store-connect-api-token-cli --bin apple_app_store_connect_api_token_gen -- --individual 'key_id' '/path/AuthKey_xxx.p8'
 
 Or
 
 cargo install apple-app-store-connect-api-token-cli
 apple_app_store_connect_api_token_gen 'key_id' '/path/AuthKey_xxx.p8' 'issuer_id'
+apple_app_store_connect_api_token_gen --individual 'key_id' '/path/AuthKey_xxx.p8'
+
+The key may also be given as `env:NAME`, `base64:MIGH...`, `file:/path/AuthKey_xxx.der` or inline PEM.
+Encrypted PKCS#8 keys are decrypted with the passphrase in `APPLE_PRIVATE_KEY_PASSPHRASE`, or prompted for.
 */
 
-use std::{env, fs};
+use std::env;
 
-use apple_app_store_connect_api_token::create;
+use apple_app_store_connect_api_token::{apple_jwt_core::KeySource, create_with_signer, KeyType};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let key_id = env::args().nth(1).unwrap();
-    let auth_key_path = env::args().nth(2).unwrap();
-    let issuer_id = env::args().nth(3).unwrap();
-
-    let auth_key_bytes = fs::read(auth_key_path)?;
-
-    let token = create(key_id, auth_key_bytes, issuer_id, None, None, None)?;
+    let mut args = env::args().skip(1).collect::<Vec<_>>();
+    let individual = match args.iter().position(|x| x == "--individual") {
+        Some(i) => {
+            args.remove(i);
+            true
+        }
+        None => false,
+    };
+    let mut args = args.into_iter();
+
+    let key_id = args.next().unwrap();
+    let auth_key_source = args.next().unwrap().parse::<KeySource>()?;
+    let key_type = if individual {
+        KeyType::Individual
+    } else {
+        KeyType::from(args.next().unwrap())
+    };
+
+    let auth_key = auth_key_source
+        .load_with_passphrase_prompt(|| rpassword::prompt_password("Passphrase: "))?;
+
+    let token = create_with_signer(key_id, &auth_key, key_type, None, None, None)?;
 
     println!("{token}");
 
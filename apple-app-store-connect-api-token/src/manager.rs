@@ -0,0 +1,150 @@
+use core::time::Duration;
+use std::sync::Arc;
+
+use arc_swap::ArcSwapOption;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+
+use crate::{CreateError, Generator, EXPIRATION_TIME_DURATION_SECONDS_MAX_FOR_MOST_REQUESTS};
+
+//
+const REFRESH_AHEAD_DUR: Duration = Duration::from_secs(60 * 5);
+
+//
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub value: Box<str>,
+    pub issued_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Caches the current token and re-mints it `refresh_ahead_dur` before it expires.
+#[derive(Debug)]
+pub struct Manager {
+    generator: Generator,
+    scope: Option<Vec<Box<str>>>,
+    expiration_time_dur: Duration,
+    refresh_ahead_dur: Duration,
+    storage: ArcSwapOption<Token>,
+}
+
+impl Manager {
+    pub fn new(generator: Generator) -> Self {
+        Self {
+            generator,
+            scope: None,
+            expiration_time_dur: Duration::from_secs(
+                EXPIRATION_TIME_DURATION_SECONDS_MAX_FOR_MOST_REQUESTS,
+            ),
+            refresh_ahead_dur: REFRESH_AHEAD_DUR,
+            storage: ArcSwapOption::empty(),
+        }
+    }
+
+    pub fn with_scope(mut self, scope: impl Into<Option<Vec<Box<str>>>>) -> Self {
+        self.scope = scope.into();
+        self
+    }
+
+    pub fn with_expiration_time_dur(mut self, dur: Duration) -> Self {
+        self.expiration_time_dur = dur;
+        self
+    }
+
+    /// Capped at half of each token's lifetime, so a too large value cannot re-mint on every call.
+    pub fn with_refresh_ahead_dur(mut self, dur: Duration) -> Self {
+        self.refresh_ahead_dur = dur;
+        self
+    }
+
+    pub fn set(&self, token: Token) {
+        self.storage.store(Some(Arc::new(token)));
+    }
+
+    pub fn clear(&self) {
+        self.storage.store(None);
+    }
+
+    /// The cached token, if it is not about to expire.
+    pub fn get(&self) -> Option<Arc<Token>> {
+        let refresh_ahead_dur = ChronoDuration::seconds(self.refresh_ahead_dur.as_secs() as i64);
+
+        self.storage.load_full().filter(|token| {
+            let refresh_ahead_dur = refresh_ahead_dur.min((token.expires_at - token.issued_at) / 2);
+            Utc::now() + refresh_ahead_dur < token.expires_at
+        })
+    }
+
+    /// The cached token value, re-minted first when it is missing or about to expire.
+    pub fn get_value(&self) -> Result<Box<str>, CreateError> {
+        match self.get() {
+            Some(token) => Ok(token.value.to_owned()),
+            None => self.refresh().map(|token| token.value.to_owned()),
+        }
+    }
+
+    pub fn refresh(&self) -> Result<Arc<Token>, CreateError> {
+        let claims =
+            self.generator
+                .claims(self.scope.to_owned(), None, self.expiration_time_dur)?;
+        let value = self.generator.encode(&claims)?;
+
+        let token = Arc::new(Token {
+            value,
+            issued_at: claims.iat,
+            expires_at: claims.exp,
+        });
+        self.storage.store(Some(token.clone()));
+
+        Ok(token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::tests::generator;
+
+    #[test]
+    fn test_get_value() {
+        let manager = Manager::new(generator());
+
+        assert!(manager.get().is_none());
+        let value = manager.get_value().unwrap();
+        assert_eq!(manager.get_value().unwrap(), value);
+        let token = manager.get().unwrap();
+        assert_eq!(
+            (token.expires_at - token.issued_at).num_seconds() as u64,
+            EXPIRATION_TIME_DURATION_SECONDS_MAX_FOR_MOST_REQUESTS
+        );
+
+        // about to expire
+        manager.set(Token {
+            value: "foo".into(),
+            issued_at: Utc::now() - ChronoDuration::minutes(18),
+            expires_at: Utc::now() + ChronoDuration::minutes(2),
+        });
+        assert_ne!(manager.get_value().unwrap().as_ref(), "foo");
+
+        // still fresh
+        manager.set(Token {
+            value: "bar".into(),
+            issued_at: Utc::now(),
+            expires_at: Utc::now() + ChronoDuration::minutes(20),
+        });
+        assert_eq!(manager.get_value().unwrap().as_ref(), "bar");
+
+        manager.clear();
+        assert!(manager.get().is_none());
+    }
+
+    #[test]
+    fn test_with_refresh_ahead_dur() {
+        // Longer than the token lifetime.
+        let manager =
+            Manager::new(generator()).with_refresh_ahead_dur(Duration::from_secs(60 * 60));
+
+        let value = manager.get_value().unwrap();
+        assert_eq!(manager.get_value().unwrap(), value);
+    }
+}
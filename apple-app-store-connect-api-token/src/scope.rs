@@ -0,0 +1,281 @@
+//! [Doc](https://developer.apple.com/documentation/appstoreconnectapi/generating_tokens_for_api_requests#3878467)
+
+use core::{fmt, str::FromStr};
+
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Get,
+    Post,
+    Patch,
+    Delete,
+}
+
+impl Method {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Get => "GET",
+            Self::Post => "POST",
+            Self::Patch => "PATCH",
+            Self::Delete => "DELETE",
+        }
+    }
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Method {
+    type Err = ScopeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "GET" => Ok(Self::Get),
+            "POST" => Ok(Self::Post),
+            "PATCH" => Ok(Self::Patch),
+            "DELETE" => Ok(Self::Delete),
+            _ => Err(ScopeError::MethodInvalid(s.into())),
+        }
+    }
+}
+
+/// One `scope` entry, e.g. `GET /v1/apps?filter[platform]=IOS`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScopeEntry {
+    pub method: Method,
+    pub path: Box<str>,
+    pub query: Vec<(Box<str>, Box<str>)>,
+}
+
+impl ScopeEntry {
+    pub fn new(method: Method, path: impl AsRef<str>) -> Self {
+        Self {
+            method,
+            path: path.as_ref().into(),
+            query: vec![],
+        }
+    }
+
+    pub fn with_query(mut self, key: impl AsRef<str>, value: impl AsRef<str>) -> Self {
+        self.query
+            .push((key.as_ref().into(), value.as_ref().into()));
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), ScopeError> {
+        let path = self.path.as_ref();
+        let version_len = path
+            .strip_prefix("/v")
+            .map(|x| x.chars().take_while(|c| c.is_ascii_digit()).count())
+            .unwrap_or(0);
+        if version_len == 0
+            || !matches!(path.as_bytes().get(2 + version_len), Some(b'/'))
+            || path.ends_with('/')
+            || path.contains("//")
+            || path.contains(|c: char| c.is_whitespace() || c == '?' || c == '#')
+        {
+            return Err(ScopeError::PathInvalid(self.path.to_owned()));
+        }
+
+        if let Some((key, _)) = self.query.iter().find(|(key, _)| key.is_empty()) {
+            return Err(ScopeError::QueryInvalid(key.to_owned()));
+        }
+
+        Ok(())
+    }
+
+    /// Validated and rendered as [`Claims::scope`](crate::Claims::scope) expects.
+    pub fn build(&self) -> Result<Box<str>, ScopeError> {
+        self.validate()?;
+        Ok(self.to_string().into())
+    }
+}
+
+impl fmt::Display for ScopeEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.method, self.path)?;
+        for (i, (key, value)) in self.query.iter().enumerate() {
+            f.write_str(if i == 0 { "?" } else { "&" })?;
+            write_encoded(f, key)?;
+            f.write_str("=")?;
+            write_encoded(f, value)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ScopeEntry {
+    type Err = ScopeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (method, rest) = s
+            .split_once(' ')
+            .ok_or_else(|| ScopeError::FormatInvalid(s.into()))?;
+        let method = method.parse::<Method>()?;
+
+        let (path, query) = match rest.split_once('?') {
+            Some((path, query)) => (path, Some(query)),
+            None => (rest, None),
+        };
+
+        let mut entry = Self::new(method, path);
+        if let Some(query) = query {
+            for pair in query.split('&') {
+                let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                entry = entry.with_query(decode(key)?, decode(value)?);
+            }
+        }
+
+        entry.validate()?;
+
+        // Anything that does not survive a round trip is not in the form Apple compares against.
+        if entry.to_string() != s {
+            return Err(ScopeError::FormatInvalid(s.into()));
+        }
+
+        Ok(entry)
+    }
+}
+
+/// Checks raw [`Claims::scope`](crate::Claims::scope) entries before a token is minted.
+pub fn validate(scope: &[Box<str>]) -> Result<(), ScopeError> {
+    scope
+        .iter()
+        .try_for_each(|x| x.parse::<ScopeEntry>().map(|_| ()))
+}
+
+//
+fn is_unreserved(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_' | b'~' | b'[' | b']' | b',')
+}
+
+fn write_encoded(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    for b in s.bytes() {
+        if is_unreserved(b) {
+            write!(f, "{}", b as char)?;
+        } else {
+            write!(f, "%{b:02X}")?;
+        }
+    }
+    Ok(())
+}
+
+fn decode(s: &str) -> Result<String, ScopeError> {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let b = s
+                .get(i + 1..i + 3)
+                .and_then(|x| u8::from_str_radix(x, 16).ok())
+                .ok_or_else(|| ScopeError::QueryInvalid(s.into()))?;
+            out.push(b);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(out).map_err(|_| ScopeError::QueryInvalid(s.into()))
+}
+
+//
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScopeError {
+    FormatInvalid(Box<str>),
+    MethodInvalid(Box<str>),
+    PathInvalid(Box<str>),
+    QueryInvalid(Box<str>),
+}
+impl fmt::Display for ScopeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+impl std::error::Error for ScopeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build() {
+        assert_eq!(
+            ScopeEntry::new(Method::Get, "/v1/apps")
+                .with_query("filter[platform]", "IOS")
+                .build()
+                .unwrap()
+                .as_ref(),
+            "GET /v1/apps?filter[platform]=IOS"
+        );
+        assert_eq!(
+            ScopeEntry::new(Method::Get, "/v1/salesReports")
+                .with_query("filter[frequency]", "DAILY")
+                .with_query("filter[vendorNumber]", "123456")
+                .with_query("filter[name]", "a b&c")
+                .build()
+                .unwrap()
+                .as_ref(),
+            "GET /v1/salesReports?filter[frequency]=DAILY&filter[vendorNumber]=123456&filter[name]=a%20b%26c"
+        );
+        assert_eq!(
+            ScopeEntry::new(Method::Patch, "/v1/apps/123")
+                .build()
+                .unwrap()
+                .as_ref(),
+            "PATCH /v1/apps/123"
+        );
+
+        for path in [
+            "",
+            "/",
+            "apps",
+            "/apps",
+            "/v1",
+            "/v1/",
+            "/v1/apps ",
+            "/v1//apps",
+        ] {
+            assert_eq!(
+                ScopeEntry::new(Method::Get, path).build(),
+                Err(ScopeError::PathInvalid(path.into()))
+            );
+        }
+        assert!(matches!(
+            ScopeEntry::new(Method::Get, "/v1/apps")
+                .with_query("", "IOS")
+                .build(),
+            Err(ScopeError::QueryInvalid(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse() {
+        for s in [
+            "GET /v1/apps",
+            "GET /v1/apps?filter[platform]=IOS",
+            "GET /v1/apps?filter[platform]=IOS,MAC_OS&limit=200",
+            "DELETE /v1/betaTesters/123",
+        ] {
+            assert_eq!(s.parse::<ScopeEntry>().unwrap().to_string(), s);
+        }
+
+        for s in [
+            "GET",
+            "get /v1/apps",
+            "PUT /v1/apps",
+            "GET  /v1/apps",
+            "GET https://api.appstoreconnect.apple.com/v1/apps",
+            "GET /v1/apps?filter[platform]=IOS MAC_OS",
+            "GET /v1/apps?filter%5Bplatform%5D=IOS",
+            "GET /v1/apps?filter[platform]=%ZZ",
+            "GET /v1/apps#foo",
+        ] {
+            assert!(s.parse::<ScopeEntry>().is_err(), "{s}");
+        }
+    }
+}
@@ -0,0 +1,23 @@
+//! ECDSA P-256, RSA PKCS#1 v1.5 verification, SHA-256 and random bytes, provided by OpenSSL (`with-openssl`, the default)
+//! or by pure-Rust crates (`with-rustcrypto`).
+
+#[cfg(feature = "with-openssl")]
+mod openssl;
+#[cfg(feature = "with-openssl")]
+pub use self::openssl::BackendError;
+#[cfg(feature = "with-openssl")]
+pub(crate) use self::openssl::{fill_random, sha256, PrivateKey, PublicKey, RsaPublicKey};
+
+#[cfg(all(feature = "with-rustcrypto", not(feature = "with-openssl")))]
+mod rustcrypto;
+#[cfg(all(feature = "with-rustcrypto", not(feature = "with-openssl")))]
+pub use self::rustcrypto::BackendError;
+#[cfg(all(feature = "with-rustcrypto", not(feature = "with-openssl")))]
+pub(crate) use self::rustcrypto::{fill_random, sha256, PrivateKey, PublicKey, RsaPublicKey};
+
+#[cfg(not(any(feature = "with-openssl", feature = "with-rustcrypto")))]
+compile_error!(
+    "apple-jwt-core requires either the `with-openssl` or the `with-rustcrypto` feature"
+);
+
+pub(crate) const COORDINATE_LEN: usize = 32;
@@ -0,0 +1,183 @@
+use openssl::{
+    bn::{BigNum, BigNumContext},
+    ec::EcKey,
+    ecdsa::EcdsaSig,
+    hash::MessageDigest,
+    nid::Nid,
+    pkey::{HasParams, PKey, Private, Public},
+    rsa::Rsa,
+    sign::{Signer as OpensslSigner, Verifier},
+};
+
+use super::COORDINATE_LEN;
+use crate::key::{KeyError, SignError, ES256_SIGNATURE_LEN};
+
+pub type BackendError = openssl::error::ErrorStack;
+
+//
+#[derive(Clone)]
+pub(crate) struct PrivateKey(PKey<Private>);
+
+impl PrivateKey {
+    pub(crate) fn from_pem(pem_bytes: &[u8]) -> Result<Self, KeyError> {
+        let pkey = PKey::private_key_from_pem(pem_bytes).map_err(KeyError::ParseFailed)?;
+        check_curve(&pkey)?;
+
+        Ok(Self(pkey))
+    }
+
+    pub(crate) fn from_der(der_bytes: &[u8]) -> Result<Self, KeyError> {
+        let pkey = PKey::private_key_from_der(der_bytes).map_err(KeyError::ParseFailed)?;
+        check_curve(&pkey)?;
+
+        Ok(Self(pkey))
+    }
+
+    pub(crate) fn from_encrypted_pem(
+        pem_bytes: &[u8],
+        passphrase: &[u8],
+    ) -> Result<Self, KeyError> {
+        let pkey = PKey::private_key_from_pem_passphrase(pem_bytes, passphrase)
+            .map_err(KeyError::DecryptFailed)?;
+        check_curve(&pkey)?;
+
+        Ok(Self(pkey))
+    }
+
+    pub(crate) fn from_encrypted_der(
+        der_bytes: &[u8],
+        passphrase: &[u8],
+    ) -> Result<Self, KeyError> {
+        let pkey = PKey::private_key_from_pkcs8_passphrase(der_bytes, passphrase)
+            .map_err(KeyError::DecryptFailed)?;
+        check_curve(&pkey)?;
+
+        Ok(Self(pkey))
+    }
+
+    pub(crate) fn public_key(&self) -> Result<PublicKey, KeyError> {
+        let ec_key = self.0.ec_key().map_err(KeyError::ParseFailed)?;
+        let pkey = EcKey::from_public_key(ec_key.group(), ec_key.public_key())
+            .and_then(PKey::from_ec_key)
+            .map_err(KeyError::ParseFailed)?;
+
+        Ok(PublicKey(pkey))
+    }
+
+    pub(crate) fn sign(&self, message: &[u8]) -> Result<[u8; ES256_SIGNATURE_LEN], SignError> {
+        let der = OpensslSigner::new(MessageDigest::sha256(), &self.0)
+            .and_then(|mut signer| signer.sign_oneshot_to_vec(message))
+            .map_err(SignError::SignFailed)?;
+
+        let sig = EcdsaSig::from_der(&der).map_err(SignError::SignFailed)?;
+
+        let mut signature = [0; ES256_SIGNATURE_LEN];
+        signature[..COORDINATE_LEN].copy_from_slice(
+            &sig.r()
+                .to_vec_padded(COORDINATE_LEN as i32)
+                .map_err(SignError::SignFailed)?,
+        );
+        signature[COORDINATE_LEN..].copy_from_slice(
+            &sig.s()
+                .to_vec_padded(COORDINATE_LEN as i32)
+                .map_err(SignError::SignFailed)?,
+        );
+
+        Ok(signature)
+    }
+}
+
+//
+#[derive(Clone)]
+pub(crate) struct PublicKey(PKey<Public>);
+
+impl PublicKey {
+    pub(crate) fn from_pem(pem_bytes: &[u8]) -> Result<Self, KeyError> {
+        let pkey = PKey::public_key_from_pem(pem_bytes).map_err(KeyError::ParseFailed)?;
+        check_curve(&pkey)?;
+
+        Ok(Self(pkey))
+    }
+
+    pub(crate) fn to_pem(&self) -> Result<String, KeyError> {
+        let pem = self.0.public_key_to_pem().map_err(KeyError::ParseFailed)?;
+
+        Ok(String::from_utf8_lossy(&pem).into_owned())
+    }
+
+    pub(crate) fn coordinates(
+        &self,
+    ) -> Result<([u8; COORDINATE_LEN], [u8; COORDINATE_LEN]), KeyError> {
+        let ec_key = self.0.ec_key().map_err(KeyError::ParseFailed)?;
+
+        (|| {
+            let mut ctx = BigNumContext::new()?;
+            let mut x = BigNum::new()?;
+            let mut y = BigNum::new()?;
+            ec_key
+                .public_key()
+                .affine_coordinates(ec_key.group(), &mut x, &mut y, &mut ctx)?;
+
+            let mut coordinates = ([0; COORDINATE_LEN], [0; COORDINATE_LEN]);
+            coordinates
+                .0
+                .copy_from_slice(&x.to_vec_padded(COORDINATE_LEN as i32)?);
+            coordinates
+                .1
+                .copy_from_slice(&y.to_vec_padded(COORDINATE_LEN as i32)?);
+            Ok(coordinates)
+        })()
+        .map_err(KeyError::ParseFailed)
+    }
+
+    pub(crate) fn verify(&self, message: &[u8], signature: &[u8; ES256_SIGNATURE_LEN]) -> bool {
+        let (r, s) = signature.split_at(COORDINATE_LEN);
+
+        (|| {
+            let der =
+                EcdsaSig::from_private_components(BigNum::from_slice(r)?, BigNum::from_slice(s)?)?
+                    .to_der()?;
+            Verifier::new(MessageDigest::sha256(), &self.0)?.verify_oneshot(&der, message)
+        })()
+        .unwrap_or(false)
+    }
+}
+
+//
+#[derive(Clone)]
+pub(crate) struct RsaPublicKey(PKey<Public>);
+
+impl RsaPublicKey {
+    pub(crate) fn from_components(n: &[u8], e: &[u8]) -> Result<Self, KeyError> {
+        let pkey = (|| {
+            let rsa = Rsa::from_public_components(BigNum::from_slice(n)?, BigNum::from_slice(e)?)?;
+            PKey::from_rsa(rsa)
+        })()
+        .map_err(KeyError::ParseFailed)?;
+
+        Ok(Self(pkey))
+    }
+
+    pub(crate) fn verify_rs256(&self, message: &[u8], signature: &[u8]) -> bool {
+        Verifier::new(MessageDigest::sha256(), &self.0)
+            .and_then(|mut verifier| verifier.verify_oneshot(signature, message))
+            .unwrap_or(false)
+    }
+}
+
+fn check_curve<T: HasParams>(pkey: &PKey<T>) -> Result<(), KeyError> {
+    let ec_key = pkey.ec_key().map_err(|_| KeyError::NotEcKey)?;
+    if ec_key.group().curve_name() != Some(Nid::X9_62_PRIME256V1) {
+        return Err(KeyError::UnsupportedCurve);
+    }
+
+    Ok(())
+}
+
+pub(crate) fn sha256(bytes: &[u8]) -> [u8; 32] {
+    openssl::sha::sha256(bytes)
+}
+
+pub(crate) fn fill_random(buf: &mut [u8]) -> Result<(), BackendError> {
+    openssl::rand::rand_bytes(buf)
+}
@@ -0,0 +1,188 @@
+use p256::{
+    ecdsa::{
+        signature::{Signer as _, Verifier as _},
+        Signature, SigningKey, VerifyingKey,
+    },
+    elliptic_curve::{sec1::ToEncodedPoint as _, ALGORITHM_OID},
+    pkcs8::{
+        spki::{DecodePublicKey as _, EncodePublicKey as _, SubjectPublicKeyInfoRef},
+        AssociatedOid as _, DecodePrivateKey as _, Document, EncryptedPrivateKeyInfo, LineEnding,
+        ObjectIdentifier, PrivateKeyInfo, SecretDocument,
+    },
+    NistP256, SecretKey,
+};
+use rand_core::{OsRng, RngCore as _};
+use rsa::{pkcs1v15, BigUint};
+use sha2::{Digest as _, Sha256};
+
+use super::COORDINATE_LEN;
+use crate::key::{KeyError, SignError, ES256_SIGNATURE_LEN};
+
+pub type BackendError = Box<dyn std::error::Error + Send + Sync>;
+
+const PEM_LABEL_SEC1: &str = "EC PRIVATE KEY";
+
+//
+#[derive(Clone)]
+pub(crate) struct PrivateKey(SecretKey);
+
+impl PrivateKey {
+    pub(crate) fn from_pem(pem_bytes: &[u8]) -> Result<Self, KeyError> {
+        let (label, document) =
+            SecretDocument::from_pem(pem_str(pem_bytes)?).map_err(parse_failed)?;
+
+        if label == PEM_LABEL_SEC1 {
+            SecretKey::from_sec1_der(document.as_bytes())
+                .map(Self)
+                .map_err(parse_failed)
+        } else {
+            Self::from_pkcs8_der(document.as_bytes())
+        }
+    }
+
+    pub(crate) fn from_der(der_bytes: &[u8]) -> Result<Self, KeyError> {
+        match Self::from_pkcs8_der(der_bytes) {
+            Err(KeyError::ParseFailed(_)) => SecretKey::from_sec1_der(der_bytes)
+                .map(Self)
+                .map_err(parse_failed),
+            x => x,
+        }
+    }
+
+    pub(crate) fn from_encrypted_pem(
+        pem_bytes: &[u8],
+        passphrase: &[u8],
+    ) -> Result<Self, KeyError> {
+        let (_, document) = Document::from_pem(pem_str(pem_bytes)?).map_err(parse_failed)?;
+
+        Self::from_encrypted_der(document.as_bytes(), passphrase)
+    }
+
+    pub(crate) fn from_encrypted_der(
+        der_bytes: &[u8],
+        passphrase: &[u8],
+    ) -> Result<Self, KeyError> {
+        let document = EncryptedPrivateKeyInfo::try_from(der_bytes)
+            .map_err(parse_failed)?
+            .decrypt(passphrase)
+            .map_err(|err| KeyError::DecryptFailed(Box::new(err)))?;
+
+        Self::from_pkcs8_der(document.as_bytes())
+    }
+
+    fn from_pkcs8_der(der_bytes: &[u8]) -> Result<Self, KeyError> {
+        let info = PrivateKeyInfo::try_from(der_bytes).map_err(parse_failed)?;
+        check_curve(info.algorithm.oid, info.algorithm.parameters_oid().ok())?;
+
+        SecretKey::from_pkcs8_der(der_bytes)
+            .map(Self)
+            .map_err(parse_failed)
+    }
+
+    pub(crate) fn public_key(&self) -> Result<PublicKey, KeyError> {
+        Ok(PublicKey(self.0.public_key()))
+    }
+
+    pub(crate) fn sign(&self, message: &[u8]) -> Result<[u8; ES256_SIGNATURE_LEN], SignError> {
+        let signature: Signature = SigningKey::from(&self.0)
+            .try_sign(message)
+            .map_err(|err| SignError::SignFailed(err.into()))?;
+
+        Ok(signature.to_bytes().into())
+    }
+}
+
+//
+#[derive(Clone)]
+pub(crate) struct PublicKey(p256::PublicKey);
+
+impl PublicKey {
+    pub(crate) fn from_pem(pem_bytes: &[u8]) -> Result<Self, KeyError> {
+        let (_, document) = Document::from_pem(pem_str(pem_bytes)?).map_err(parse_failed)?;
+
+        let info = SubjectPublicKeyInfoRef::try_from(document.as_bytes()).map_err(parse_failed)?;
+        check_curve(info.algorithm.oid, info.algorithm.parameters_oid().ok())?;
+
+        p256::PublicKey::from_public_key_der(document.as_bytes())
+            .map(Self)
+            .map_err(parse_failed)
+    }
+
+    pub(crate) fn to_pem(&self) -> Result<String, KeyError> {
+        self.0
+            .to_public_key_pem(LineEnding::LF)
+            .map_err(parse_failed)
+    }
+
+    pub(crate) fn coordinates(
+        &self,
+    ) -> Result<([u8; COORDINATE_LEN], [u8; COORDINATE_LEN]), KeyError> {
+        let point = self.0.to_encoded_point(false);
+        match (point.x(), point.y()) {
+            (Some(x), Some(y)) => Ok(((*x).into(), (*y).into())),
+            _ => Err(KeyError::NotEcKey),
+        }
+    }
+
+    pub(crate) fn verify(&self, message: &[u8], signature: &[u8; ES256_SIGNATURE_LEN]) -> bool {
+        Signature::from_slice(signature)
+            .map(|signature| {
+                VerifyingKey::from(&self.0)
+                    .verify(message, &signature)
+                    .is_ok()
+            })
+            .unwrap_or(false)
+    }
+}
+
+//
+#[derive(Clone)]
+pub(crate) struct RsaPublicKey(pkcs1v15::VerifyingKey<Sha256>);
+
+impl RsaPublicKey {
+    pub(crate) fn from_components(n: &[u8], e: &[u8]) -> Result<Self, KeyError> {
+        rsa::RsaPublicKey::new(BigUint::from_bytes_be(n), BigUint::from_bytes_be(e))
+            .map(|public_key| Self(pkcs1v15::VerifyingKey::new(public_key)))
+            .map_err(parse_failed)
+    }
+
+    pub(crate) fn verify_rs256(&self, message: &[u8], signature: &[u8]) -> bool {
+        pkcs1v15::Signature::try_from(signature)
+            .map(|signature| self.0.verify(message, &signature).is_ok())
+            .unwrap_or(false)
+    }
+}
+
+// Tolerates surrounding whitespace and any text before the armor, like OpenSSL does.
+fn pem_str(pem_bytes: &[u8]) -> Result<&str, KeyError> {
+    let pem = core::str::from_utf8(pem_bytes).map_err(parse_failed)?;
+    let start = pem.find("-----BEGIN").unwrap_or(0);
+
+    Ok(pem[start..].trim_end())
+}
+
+fn check_curve(
+    algorithm_oid: ObjectIdentifier,
+    parameters_oid: Option<ObjectIdentifier>,
+) -> Result<(), KeyError> {
+    if algorithm_oid != ALGORITHM_OID {
+        return Err(KeyError::NotEcKey);
+    }
+    if parameters_oid != Some(NistP256::OID) {
+        return Err(KeyError::UnsupportedCurve);
+    }
+
+    Ok(())
+}
+
+fn parse_failed(err: impl std::error::Error + Send + Sync + 'static) -> KeyError {
+    KeyError::ParseFailed(Box::new(err))
+}
+
+pub(crate) fn sha256(bytes: &[u8]) -> [u8; 32] {
+    Sha256::digest(bytes).into()
+}
+
+pub(crate) fn fill_random(buf: &mut [u8]) -> Result<(), BackendError> {
+    OsRng.try_fill_bytes(buf).map_err(Into::into)
+}
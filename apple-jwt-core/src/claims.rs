@@ -0,0 +1,8 @@
+use chrono::{DateTime, Utc};
+
+/// The registered claims every Apple token carries.
+pub trait RegisteredClaims {
+    fn aud(&self) -> &str;
+    fn iat(&self) -> DateTime<Utc>;
+    fn exp(&self) -> DateTime<Utc>;
+}
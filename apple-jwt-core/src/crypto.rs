@@ -0,0 +1,29 @@
+//! SHA-256 and random bytes from the enabled backend.
+
+use crate::backend::{self, BackendError};
+
+pub fn sha256(bytes: impl AsRef<[u8]>) -> [u8; 32] {
+    backend::sha256(bytes.as_ref())
+}
+
+/// From the operating system's CSPRNG.
+pub fn fill_random(buf: &mut [u8]) -> Result<(), BackendError> {
+    backend::fill_random(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sha256_and_fill_random() {
+        // printf foo | sha256sum
+        assert_eq!(sha256("foo")[..4], [0x2c, 0x26, 0xb4, 0x6b]);
+
+        let mut a = [0; 32];
+        let mut b = [0; 32];
+        fill_random(&mut a).unwrap();
+        fill_random(&mut b).unwrap();
+        assert_ne!(a, b);
+    }
+}
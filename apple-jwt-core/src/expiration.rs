@@ -0,0 +1,206 @@
+use core::time::Duration;
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+
+/// Clock skew tolerated before `issued_at` counts as in the future.
+pub const ISSUED_AT_LEEWAY: Duration = Duration::from_secs(60);
+
+/// `issued_at + dur`, where `dur` falls back to `default_dur` and is clamped to `max_dur`.
+pub fn calculate(
+    issued_at: DateTime<Utc>,
+    dur: impl Into<Option<Duration>>,
+    default_dur: Duration,
+    max_dur: Duration,
+) -> DateTime<Utc> {
+    let dur = dur.into().unwrap_or(default_dur).min(max_dur);
+
+    issued_at + ChronoDuration::seconds(dur.as_secs() as i64)
+}
+
+//
+#[derive(Clone, Default)]
+pub enum ExpirationPolicy {
+    /// Silently clamp the lifetime to the maximum.
+    #[default]
+    Clamp,
+    /// Fail on any violation.
+    Reject,
+    /// Report each violation to the callback, then clamp.
+    Warn(Arc<dyn Fn(&ExpirationViolation) + Send + Sync>),
+}
+
+impl ExpirationPolicy {
+    pub fn warn(callback: impl Fn(&ExpirationViolation) + Send + Sync + 'static) -> Self {
+        Self::Warn(Arc::new(callback))
+    }
+
+    /// Like [`calculate`], but also checks that `issued_at` is not more than [`ISSUED_AT_LEEWAY`]
+    /// in the future unless clamping.
+    pub fn apply(
+        &self,
+        issued_at: DateTime<Utc>,
+        dur: impl Into<Option<Duration>>,
+        default_dur: Duration,
+        max_dur: Duration,
+    ) -> Result<DateTime<Utc>, ExpirationViolation> {
+        let dur = dur.into().unwrap_or(default_dur);
+
+        let mut violations = vec![];
+        if dur > max_dur {
+            violations.push(ExpirationViolation::DurationExceedsMax {
+                requested: dur,
+                max: max_dur,
+            });
+        }
+        let now = Utc::now();
+        if issued_at > now + ChronoDuration::seconds(ISSUED_AT_LEEWAY.as_secs() as i64) {
+            violations.push(ExpirationViolation::IssuedAtInFuture { issued_at, now });
+        }
+
+        match self {
+            Self::Clamp => {}
+            Self::Reject => {
+                if let Some(violation) = violations.into_iter().next() {
+                    return Err(violation);
+                }
+            }
+            Self::Warn(callback) => violations.iter().for_each(|x| callback(x)),
+        }
+
+        Ok(calculate(issued_at, dur, default_dur, max_dur))
+    }
+}
+
+impl core::fmt::Debug for ExpirationPolicy {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Clamp => write!(f, "Clamp"),
+            Self::Reject => write!(f, "Reject"),
+            Self::Warn(_) => write!(f, "Warn"),
+        }
+    }
+}
+
+//
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExpirationViolation {
+    DurationExceedsMax {
+        requested: Duration,
+        max: Duration,
+    },
+    IssuedAtInFuture {
+        issued_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+    },
+}
+impl core::fmt::Display for ExpirationViolation {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+impl std::error::Error for ExpirationViolation {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_calculate() {
+        let issued_at = "2022-06-06T00:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let default_dur = Duration::from_secs(60);
+        let max_dur = Duration::from_secs(120);
+
+        assert_eq!(
+            calculate(issued_at, None, default_dur, max_dur),
+            "2022-06-06T00:01:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        assert_eq!(
+            calculate(issued_at, Duration::from_secs(90), default_dur, max_dur),
+            "2022-06-06T00:01:30Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        assert_eq!(
+            calculate(issued_at, Duration::from_secs(3600), default_dur, max_dur),
+            "2022-06-06T00:02:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_apply() {
+        let issued_at = "2022-06-06T00:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let future_issued_at = Utc::now() + ChronoDuration::hours(1);
+        let default_dur = Duration::from_secs(60);
+        let max_dur = Duration::from_secs(120);
+        let too_long_dur = Duration::from_secs(3600);
+
+        //
+        assert_eq!(
+            ExpirationPolicy::Clamp
+                .apply(issued_at, too_long_dur, default_dur, max_dur)
+                .unwrap(),
+            "2022-06-06T00:02:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        ExpirationPolicy::Clamp
+            .apply(future_issued_at, None, default_dur, max_dur)
+            .unwrap();
+
+        //
+        assert_eq!(
+            ExpirationPolicy::Reject
+                .apply(issued_at, Duration::from_secs(90), default_dur, max_dur)
+                .unwrap(),
+            "2022-06-06T00:01:30Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        assert_eq!(
+            ExpirationPolicy::Reject.apply(issued_at, too_long_dur, default_dur, max_dur),
+            Err(ExpirationViolation::DurationExceedsMax {
+                requested: too_long_dur,
+                max: max_dur
+            })
+        );
+        match ExpirationPolicy::Reject.apply(future_issued_at, None, default_dur, max_dur) {
+            Err(ExpirationViolation::IssuedAtInFuture { issued_at, .. }) => {
+                assert_eq!(issued_at, future_issued_at)
+            }
+            x => panic!("{x:?}"),
+        }
+        // within the leeway, e.g. a slightly fast clock on another host
+        let skewed_issued_at = Utc::now() + ChronoDuration::seconds(30);
+        ExpirationPolicy::Reject
+            .apply(skewed_issued_at, None, default_dur, max_dur)
+            .unwrap();
+        let skewed_issued_at = Utc::now() + ChronoDuration::seconds(90);
+        assert!(matches!(
+            ExpirationPolicy::Reject.apply(skewed_issued_at, None, default_dur, max_dur),
+            Err(ExpirationViolation::IssuedAtInFuture { .. })
+        ));
+
+        //
+        let violations = Arc::new(Mutex::new(vec![]));
+        let policy = ExpirationPolicy::warn({
+            let violations = violations.clone();
+            move |x| violations.lock().unwrap().push(x.to_owned())
+        });
+        assert_eq!(
+            policy
+                .apply(issued_at, too_long_dur, default_dur, max_dur)
+                .unwrap(),
+            "2022-06-06T00:02:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        policy
+            .apply(future_issued_at, None, default_dur, max_dur)
+            .unwrap();
+        let violations = violations.lock().unwrap();
+        assert_eq!(violations.len(), 2);
+        assert!(matches!(
+            violations[0],
+            ExpirationViolation::DurationExceedsMax { .. }
+        ));
+        assert!(matches!(
+            violations[1],
+            ExpirationViolation::IssuedAtInFuture { .. }
+        ));
+    }
+}
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+pub const ALGORITHM_ES256: &str = "ES256";
+pub const ALGORITHM_RS256: &str = "RS256";
+pub const TYPE_JWT: &str = "JWT";
+
+//
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Header {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub typ: Option<Box<str>>,
+    pub alg: Box<str>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kid: Option<Box<str>>,
+}
+
+impl Header {
+    pub fn es256(key_id: impl AsRef<str>) -> Self {
+        Self {
+            typ: None,
+            alg: ALGORITHM_ES256.into(),
+            kid: Some(key_id.as_ref().into()),
+        }
+    }
+
+    pub fn with_typ(mut self, typ: impl AsRef<str>) -> Self {
+        self.typ = Some(typ.as_ref().into());
+        self
+    }
+}
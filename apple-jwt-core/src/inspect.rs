@@ -0,0 +1,142 @@
+use core::time::Duration;
+
+use chrono::Utc;
+use serde::de::DeserializeOwned;
+
+use crate::{
+    claims::RegisteredClaims,
+    header::Header,
+    jwt::{decode_unverified, DecodeError},
+};
+
+/// Header and claims of a token, decoded without checking the signature.
+#[derive(Debug, Clone)]
+pub struct Inspection<C> {
+    pub header: Header,
+    pub claims: C,
+    pub expected_audience: &'static str,
+}
+
+impl<C: RegisteredClaims> Inspection<C> {
+    /// `None` once `exp` has passed.
+    pub fn remaining_lifetime(&self) -> Option<Duration> {
+        (self.claims.exp() - Utc::now()).to_std().ok()
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.remaining_lifetime().is_none()
+    }
+
+    pub fn audience_mismatch(&self) -> Option<AudienceMismatch> {
+        if self.claims.aud() == self.expected_audience {
+            None
+        } else {
+            Some(AudienceMismatch {
+                expected: self.expected_audience,
+                actual: self.claims.aud().into(),
+            })
+        }
+    }
+}
+
+//
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AudienceMismatch {
+    pub expected: &'static str,
+    pub actual: Box<str>,
+}
+
+pub fn inspect<C: DeserializeOwned>(
+    token: &str,
+    expected_audience: &'static str,
+) -> Result<Inspection<C>, DecodeError> {
+    let (header, claims) = decode_unverified(token)?;
+
+    Ok(Inspection {
+        header,
+        claims,
+        expected_audience,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use chrono::{DateTime, Duration as ChronoDuration};
+    use serde::{Deserialize, Serialize};
+
+    use crate::{jwt::Encoder, key::tests::PKCS8_PEM, EcPrivateKey};
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    struct Claims {
+        #[serde(with = "chrono::serde::ts_seconds")]
+        iat: DateTime<Utc>,
+        #[serde(with = "chrono::serde::ts_seconds")]
+        exp: DateTime<Utc>,
+        aud: Box<str>,
+    }
+    impl RegisteredClaims for Claims {
+        fn aud(&self) -> &str {
+            &self.aud
+        }
+        fn iat(&self) -> DateTime<Utc> {
+            self.iat
+        }
+        fn exp(&self) -> DateTime<Utc> {
+            self.exp
+        }
+    }
+
+    #[test]
+    fn test_inspect() {
+        let encoder = Encoder::new(
+            Header::es256("foo"),
+            Arc::new(EcPrivateKey::from_pem(PKCS8_PEM).unwrap()),
+        )
+        .unwrap();
+
+        let now = Utc::now();
+        let token = encoder
+            .encode(&Claims {
+                iat: now,
+                exp: now + ChronoDuration::minutes(20),
+                aud: "bar".into(),
+            })
+            .unwrap();
+
+        let inspection = inspect::<Claims>(&token, "bar").unwrap();
+        assert_eq!(inspection.header.kid.as_deref(), Some("foo"));
+        assert!(inspection.remaining_lifetime().unwrap() > Duration::from_secs(60 * 19));
+        assert!(inspection.audience_mismatch().is_none());
+
+        let inspection = inspect::<Claims>(&token, "baz").unwrap();
+        assert_eq!(
+            inspection.audience_mismatch(),
+            Some(AudienceMismatch {
+                expected: "baz",
+                actual: "bar".into()
+            })
+        );
+
+        let token = encoder
+            .encode(&Claims {
+                iat: now - ChronoDuration::minutes(30),
+                exp: now - ChronoDuration::minutes(10),
+                aud: "bar".into(),
+            })
+            .unwrap();
+        assert!(inspect::<Claims>(&token, "bar").unwrap().is_expired());
+
+        assert!(matches!(
+            inspect::<Claims>("foo", "bar"),
+            Err(DecodeError::FormatInvalid)
+        ));
+        assert!(matches!(
+            inspect::<Claims>("e30.e30.", "bar"),
+            Err(DecodeError::DeserializeFailed(_))
+        ));
+    }
+}
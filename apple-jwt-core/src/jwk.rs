@@ -0,0 +1,186 @@
+//! [RFC 7517](https://www.rfc-editor.org/rfc/rfc7517) / [RFC 7638](https://www.rfc-editor.org/rfc/rfc7638)
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use serde::{Deserialize, Serialize};
+
+use std::{fs, io, path::Path};
+
+use crate::{backend::sha256, key::KeyError, public_key::RsaPublicKey};
+
+pub const KEY_TYPE_EC: &str = "EC";
+pub const CURVE_P256: &str = "P-256";
+pub const KEY_TYPE_RSA: &str = "RSA";
+
+//
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct EcJwk {
+    pub kty: Box<str>,
+    pub crv: Box<str>,
+    pub x: Box<str>,
+    pub y: Box<str>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kid: Option<Box<str>>,
+    #[serde(default, rename = "use", skip_serializing_if = "Option::is_none")]
+    pub use_: Option<Box<str>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alg: Option<Box<str>>,
+}
+
+impl EcJwk {
+    pub fn with_kid(mut self, kid: impl AsRef<str>) -> Self {
+        self.kid = Some(kid.as_ref().into());
+        self
+    }
+
+    /// base64url(SHA-256) over the required members in lexicographic order.
+    pub fn thumbprint(&self) -> String {
+        let json = format!(
+            r#"{{"crv":"{}","kty":"{}","x":"{}","y":"{}"}}"#,
+            self.crv, self.kty, self.x, self.y
+        );
+        URL_SAFE_NO_PAD.encode(sha256(json.as_bytes()))
+    }
+}
+
+impl core::fmt::Display for EcJwk {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let json = serde_json::to_string(self).map_err(|_| core::fmt::Error)?;
+        f.write_str(&json)
+    }
+}
+
+//
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RsaJwk {
+    pub kty: Box<str>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kid: Option<Box<str>>,
+    #[serde(default, rename = "use", skip_serializing_if = "Option::is_none")]
+    pub use_: Option<Box<str>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alg: Option<Box<str>>,
+    pub n: Box<str>,
+    pub e: Box<str>,
+}
+
+impl RsaJwk {
+    pub fn to_public_key(&self) -> Result<RsaPublicKey, KeyError> {
+        if self.kty.as_ref() != KEY_TYPE_RSA {
+            return Err(KeyError::NotRsaKey);
+        }
+
+        let n = URL_SAFE_NO_PAD
+            .decode(self.n.as_bytes())
+            .map_err(KeyError::Base64DecodeFailed)?;
+        let e = URL_SAFE_NO_PAD
+            .decode(self.e.as_bytes())
+            .map_err(KeyError::Base64DecodeFailed)?;
+
+        RsaPublicKey::from_components(&n, &e)
+    }
+}
+
+/// e.g. the document at `https://appleid.apple.com/auth/keys`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct JwkSet {
+    /// Keys other than RSA are skipped.
+    #[serde(deserialize_with = "deserialize_rsa_keys")]
+    pub keys: Vec<RsaJwk>,
+}
+
+fn deserialize_rsa_keys<'de, D>(deserializer: D) -> Result<Vec<RsaJwk>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct AnyJwk {
+        kty: Box<str>,
+        #[serde(default)]
+        kid: Option<Box<str>>,
+        #[serde(default, rename = "use")]
+        use_: Option<Box<str>>,
+        #[serde(default)]
+        alg: Option<Box<str>>,
+        #[serde(default)]
+        n: Option<Box<str>>,
+        #[serde(default)]
+        e: Option<Box<str>>,
+    }
+
+    Ok(Vec::<AnyJwk>::deserialize(deserializer)?
+        .into_iter()
+        .filter_map(|jwk| match jwk {
+            AnyJwk {
+                kty,
+                kid,
+                use_,
+                alg,
+                n: Some(n),
+                e: Some(e),
+            } if kty.as_ref() == KEY_TYPE_RSA => Some(RsaJwk {
+                kty,
+                kid,
+                use_,
+                alg,
+                n,
+                e,
+            }),
+            _ => None,
+        })
+        .collect())
+}
+
+impl JwkSet {
+    pub fn from_json(json: impl AsRef<[u8]>) -> Result<Self, JwkSetError> {
+        serde_json::from_slice(json.as_ref()).map_err(JwkSetError::DeserializeFailed)
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, JwkSetError> {
+        let json = fs::read(path).map_err(JwkSetError::ReadFileFailed)?;
+
+        Self::from_json(json)
+    }
+
+    pub fn find(&self, kid: &str) -> Option<&RsaJwk> {
+        self.keys.iter().find(|x| x.kid.as_deref() == Some(kid))
+    }
+}
+
+//
+#[derive(Debug)]
+pub enum JwkSetError {
+    ReadFileFailed(io::Error),
+    DeserializeFailed(serde_json::Error),
+}
+impl core::fmt::Display for JwkSetError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+impl std::error::Error for JwkSetError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jwk_set_de() {
+        let jwk_set = JwkSet::from_json(
+            r#"{"keys":[
+                {"kty":"EC","crv":"P-256","x":"x","y":"y","kid":"EC_KID"},
+                {"kty":"RSA","kid":"RSA_KID","use":"sig","alg":"RS256","n":"n","e":"AQAB"},
+                {"kty":"OKP","crv":"Ed25519","x":"x"}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(jwk_set.keys.len(), 1);
+        assert!(jwk_set.find("RSA_KID").is_some());
+        assert!(jwk_set.find("EC_KID").is_none());
+
+        let jwk_set = JwkSet::from_json(include_str!("../tests/fixtures/apple-jwks.json")).unwrap();
+        assert_eq!(
+            JwkSet::from_json(serde_json::to_vec(&jwk_set).unwrap()).unwrap(),
+            jwk_set
+        );
+    }
+}
@@ -0,0 +1,192 @@
+use std::sync::Arc;
+
+use base64::DecodeError as Base64DecodeError;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Error as SerdeJsonError;
+
+use crate::{header::Header, key::SignError, signer::Signer};
+
+/// `base64url(header).base64url(claims).base64url(signature)`
+pub fn encode<C: Serialize, S: Signer + ?Sized>(
+    header: &Header,
+    claims: &C,
+    signer: &S,
+) -> Result<String, EncodeError> {
+    encode_with_header_segment(&encode_segment(header)?, claims, signer)
+}
+
+fn encode_with_header_segment<C: Serialize, S: Signer + ?Sized>(
+    header_segment: &str,
+    claims: &C,
+    signer: &S,
+) -> Result<String, EncodeError> {
+    let mut token = header_segment.to_owned();
+    token.push('.');
+    token.push_str(&encode_segment(claims)?);
+
+    let signature = signer
+        .sign(token.as_bytes())
+        .map_err(EncodeError::SignFailed)?;
+
+    token.push('.');
+    token.push_str(&URL_SAFE_NO_PAD.encode(signature));
+
+    Ok(token)
+}
+
+fn encode_segment<T: Serialize>(value: &T) -> Result<String, EncodeError> {
+    let json = serde_json::to_vec(value).map_err(EncodeError::SerializeFailed)?;
+    Ok(URL_SAFE_NO_PAD.encode(json))
+}
+
+/// Header and claims, without checking the signature.
+pub fn decode_unverified<C: DeserializeOwned>(token: &str) -> Result<(Header, C), DecodeError> {
+    let (header_segment, claims_segment, _) = split(token)?;
+
+    Ok((
+        decode_segment(header_segment)?,
+        decode_segment(claims_segment)?,
+    ))
+}
+
+pub(crate) fn split(token: &str) -> Result<(&str, &str, &str), DecodeError> {
+    let mut split = token.split('.');
+    match (split.next(), split.next(), split.next(), split.next()) {
+        (Some(header), Some(claims), Some(signature), None) => Ok((header, claims, signature)),
+        _ => Err(DecodeError::FormatInvalid),
+    }
+}
+
+fn decode_segment<T: DeserializeOwned>(segment: &str) -> Result<T, DecodeError> {
+    let json = URL_SAFE_NO_PAD
+        .decode(segment)
+        .map_err(DecodeError::Base64DecodeFailed)?;
+    serde_json::from_slice(&json).map_err(DecodeError::DeserializeFailed)
+}
+
+//
+/// Header encoded once, signer shared; cheap to clone and safe to use from many threads.
+#[derive(Clone)]
+pub struct Encoder {
+    header: Header,
+    header_segment: Box<str>,
+    signer: Arc<dyn Signer>,
+}
+
+impl Encoder {
+    pub fn new(header: Header, signer: Arc<dyn Signer>) -> Result<Self, EncodeError> {
+        let header_segment = encode_segment(&header)?.into();
+
+        Ok(Self {
+            header,
+            header_segment,
+            signer,
+        })
+    }
+
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    pub fn signer(&self) -> &Arc<dyn Signer> {
+        &self.signer
+    }
+
+    pub fn encode<C: Serialize>(&self, claims: &C) -> Result<String, EncodeError> {
+        encode_with_header_segment(&self.header_segment, claims, &self.signer)
+    }
+}
+
+impl core::fmt::Debug for Encoder {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Encoder")
+            .field("header", &self.header)
+            .finish_non_exhaustive()
+    }
+}
+
+//
+#[derive(Debug)]
+pub enum EncodeError {
+    SerializeFailed(SerdeJsonError),
+    SignFailed(SignError),
+}
+impl core::fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+impl std::error::Error for EncodeError {}
+
+//
+#[derive(Debug)]
+pub enum DecodeError {
+    FormatInvalid,
+    Base64DecodeFailed(Base64DecodeError),
+    DeserializeFailed(SerdeJsonError),
+}
+impl core::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+impl std::error::Error for DecodeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::key::{
+        tests::{PKCS8_PEM, SEC1_PEM},
+        EcPrivateKey,
+    };
+
+    #[derive(Serialize)]
+    struct Claims {
+        sub: &'static str,
+    }
+
+    #[test]
+    fn test_encode() {
+        for pem in [SEC1_PEM, PKCS8_PEM] {
+            let key = EcPrivateKey::from_pem(pem).unwrap();
+
+            let token = encode(&Header::es256("foo"), &Claims { sub: "bar" }, &key).unwrap();
+
+            let mut split = token.split('.');
+            assert_eq!(
+                split.next().unwrap(),
+                URL_SAFE_NO_PAD.encode(r#"{"alg":"ES256","kid":"foo"}"#)
+            );
+            assert_eq!(
+                split.next().unwrap(),
+                URL_SAFE_NO_PAD.encode(r#"{"sub":"bar"}"#)
+            );
+            assert_eq!(
+                URL_SAFE_NO_PAD.decode(split.next().unwrap()).unwrap().len(),
+                64
+            );
+            assert!(split.next().is_none());
+        }
+    }
+
+    #[test]
+    fn test_encoder() {
+        let key = EcPrivateKey::from_pem(PKCS8_PEM).unwrap();
+        let encoder = Encoder::new(Header::es256("foo"), Arc::new(key)).unwrap();
+
+        std::thread::scope(|s| {
+            for _ in 0..4 {
+                s.spawn(|| {
+                    let token = encoder.encode(&Claims { sub: "bar" }).unwrap();
+                    assert!(token.starts_with(&format!(
+                        "{}.{}.",
+                        URL_SAFE_NO_PAD.encode(r#"{"alg":"ES256","kid":"foo"}"#),
+                        URL_SAFE_NO_PAD.encode(r#"{"sub":"bar"}"#)
+                    )));
+                });
+            }
+        });
+    }
+}
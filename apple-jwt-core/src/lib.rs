@@ -0,0 +1,30 @@
+//! Key loading, expiration, ES256 signing and JWT verification shared by the Apple token crates.
+
+pub use chrono;
+
+mod backend;
+pub mod claims;
+pub mod crypto;
+pub mod expiration;
+pub mod header;
+pub mod inspect;
+pub mod jwk;
+pub mod jwt;
+pub mod key;
+pub mod key_source;
+pub mod public_key;
+pub mod signer;
+pub mod verify;
+
+pub use backend::BackendError;
+pub use claims::RegisteredClaims;
+pub use expiration::{ExpirationPolicy, ExpirationViolation};
+pub use header::Header;
+pub use inspect::{AudienceMismatch, Inspection};
+pub use jwk::{EcJwk, JwkSet, JwkSetError, RsaJwk};
+pub use jwt::{decode_unverified, encode, DecodeError, EncodeError, Encoder};
+pub use key::{EcPrivateKey, KeyError, SignError};
+pub use key_source::{KeySource, KeySourceError, Passphrase};
+pub use public_key::{EcPublicKey, RsaPublicKey};
+pub use signer::Signer;
+pub use verify::{Rs256VerifyError, VerifyError};
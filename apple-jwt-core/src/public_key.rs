@@ -0,0 +1,144 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+
+use crate::{
+    backend::{PublicKey, RsaPublicKey as RsaPublicKeyInner},
+    header::ALGORITHM_ES256,
+    jwk::{EcJwk, CURVE_P256, KEY_TYPE_EC},
+    key::{KeyError, ES256_SIGNATURE_LEN},
+};
+
+//
+#[derive(Clone)]
+pub struct EcPublicKey(PublicKey);
+
+impl EcPublicKey {
+    /// Accepts SPKI (`-----BEGIN PUBLIC KEY-----`) PEM.
+    pub fn from_pem(pem_bytes: impl AsRef<[u8]>) -> Result<Self, KeyError> {
+        PublicKey::from_pem(pem_bytes.as_ref()).map(Self)
+    }
+
+    pub(crate) fn from_backend(public_key: PublicKey) -> Self {
+        Self(public_key)
+    }
+
+    /// SPKI (`-----BEGIN PUBLIC KEY-----`) PEM.
+    pub fn to_pem(&self) -> Result<String, KeyError> {
+        self.0.to_pem()
+    }
+
+    pub fn to_jwk(&self) -> Result<EcJwk, KeyError> {
+        let (x, y) = self.0.coordinates()?;
+
+        Ok(EcJwk {
+            kty: KEY_TYPE_EC.into(),
+            crv: CURVE_P256.into(),
+            x: URL_SAFE_NO_PAD.encode(x).into(),
+            y: URL_SAFE_NO_PAD.encode(y).into(),
+            kid: None,
+            use_: Some("sig".into()),
+            alg: Some(ALGORITHM_ES256.into()),
+        })
+    }
+
+    /// Checks an ES256 signature in the JWS `r || s` form.
+    pub fn verify(&self, message: &[u8], signature: &[u8]) -> bool {
+        match <&[u8; ES256_SIGNATURE_LEN]>::try_from(signature) {
+            Ok(signature) => self.0.verify(message, signature),
+            Err(_) => false,
+        }
+    }
+}
+
+impl core::fmt::Debug for EcPublicKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("EcPublicKey").finish_non_exhaustive()
+    }
+}
+
+/// Verification only, e.g. for the keys Apple publishes at `https://appleid.apple.com/auth/keys`.
+#[derive(Clone)]
+pub struct RsaPublicKey(RsaPublicKeyInner);
+
+impl RsaPublicKey {
+    /// Big-endian modulus and exponent.
+    pub fn from_components(n: &[u8], e: &[u8]) -> Result<Self, KeyError> {
+        RsaPublicKeyInner::from_components(n, e).map(Self)
+    }
+
+    /// Checks an RS256 (RSASSA-PKCS1-v1_5 with SHA-256) signature.
+    pub fn verify_rs256(&self, message: &[u8], signature: &[u8]) -> bool {
+        self.0.verify_rs256(message, signature)
+    }
+}
+
+impl core::fmt::Debug for RsaPublicKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RsaPublicKey").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{key::tests::PKCS8_PEM, EcPrivateKey};
+
+    #[test]
+    fn test_export() {
+        let public_key = EcPrivateKey::from_pem(PKCS8_PEM)
+            .unwrap()
+            .public_key()
+            .unwrap();
+
+        /*
+        openssl ec -in private-key.pem -pubout -out public-key.pem
+        */
+        assert_eq!(
+            public_key.to_pem().unwrap(),
+            "-----BEGIN PUBLIC KEY-----
+MFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAEY58v74eQFyLtu5rtCpeU4NggVSUQ
+SOcHhN744t0gWGc/xXkCSuszLaZriCQnnqq4Vx+IscLFcrjBj+ulZzKlUQ==
+-----END PUBLIC KEY-----
+"
+        );
+
+        let jwk = public_key.to_jwk().unwrap().with_kid("foo");
+        assert_eq!(
+            jwk.to_string(),
+            r#"{"kty":"EC","crv":"P-256","x":"Y58v74eQFyLtu5rtCpeU4NggVSUQSOcHhN744t0gWGc","y":"P8V5AkrrMy2ma4gkJ56quFcfiLHCxXK4wY_rpWcypVE","kid":"foo","use":"sig","alg":"ES256"}"#
+        );
+        assert_eq!(
+            jwk.thumbprint(),
+            "P4sCvBOFwQQCb6KTC9hpq9nbvWaux78E_tlaEEbBN3M"
+        );
+
+        assert_eq!(
+            EcPublicKey::from_pem(public_key.to_pem().unwrap())
+                .unwrap()
+                .to_jwk()
+                .unwrap()
+                .thumbprint(),
+            jwk.thumbprint()
+        );
+    }
+
+    #[test]
+    fn test_verify() {
+        use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+
+        let key = EcPrivateKey::from_pem(PKCS8_PEM).unwrap();
+        let public_key = key.public_key().unwrap();
+
+        assert!(public_key.verify(b"foo", &key.sign(b"foo").unwrap()));
+        assert!(!public_key.verify(b"bar", &key.sign(b"foo").unwrap()));
+        assert!(!public_key.verify(b"foo", b"foo"));
+
+        /*
+        printf foo | openssl dgst -sha256 -sign private-key.pem
+        */
+        let signature = URL_SAFE_NO_PAD
+            .decode("kM_RrtKWPuDb74H1V6sLUTvLARbyfGm7vPcshRBLG7rYk2ujFzRnfCdryglGDAKAuO0M8-5o5nrUL4_HbAJ1mA")
+            .unwrap();
+        assert!(public_key.verify(b"foo", &signature));
+    }
+}
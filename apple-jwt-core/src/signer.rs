@@ -0,0 +1,80 @@
+use std::sync::Arc;
+
+use crate::key::{EcPrivateKey, SignError, ES256_SIGNATURE_LEN};
+
+/// Produces ES256 signatures, so the private key may live outside the process (HSM, PKCS#11, KMS).
+///
+/// [`EcPrivateKey`] is the PEM-backed default.
+pub trait Signer: Send + Sync {
+    /// ES256 signature of `message` in the JWS `r || s` form.
+    fn sign(&self, message: &[u8]) -> Result<[u8; ES256_SIGNATURE_LEN], SignError>;
+}
+
+impl Signer for EcPrivateKey {
+    fn sign(&self, message: &[u8]) -> Result<[u8; ES256_SIGNATURE_LEN], SignError> {
+        EcPrivateKey::sign(self, message)
+    }
+}
+
+impl<T: Signer + ?Sized> Signer for &T {
+    fn sign(&self, message: &[u8]) -> Result<[u8; ES256_SIGNATURE_LEN], SignError> {
+        (**self).sign(message)
+    }
+}
+
+impl<T: Signer + ?Sized> Signer for Box<T> {
+    fn sign(&self, message: &[u8]) -> Result<[u8; ES256_SIGNATURE_LEN], SignError> {
+        (**self).sign(message)
+    }
+}
+
+impl<T: Signer + ?Sized> Signer for Arc<T> {
+    fn sign(&self, message: &[u8]) -> Result<[u8; ES256_SIGNATURE_LEN], SignError> {
+        (**self).sign(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::{header::Header, jwt::encode, key::tests::PKCS8_PEM};
+
+    struct SoftwareSigner {
+        key: EcPrivateKey,
+        count: AtomicUsize,
+    }
+    impl Signer for SoftwareSigner {
+        fn sign(&self, message: &[u8]) -> Result<[u8; ES256_SIGNATURE_LEN], SignError> {
+            self.count.fetch_add(1, Ordering::SeqCst);
+            self.key.sign(message)
+        }
+    }
+
+    struct UnavailableSigner;
+    impl Signer for UnavailableSigner {
+        fn sign(&self, _message: &[u8]) -> Result<[u8; ES256_SIGNATURE_LEN], SignError> {
+            Err(SignError::External("hsm unavailable".into()))
+        }
+    }
+
+    #[test]
+    fn test_signer() {
+        let signer = Arc::new(SoftwareSigner {
+            key: EcPrivateKey::from_pem(PKCS8_PEM).unwrap(),
+            count: AtomicUsize::new(0),
+        });
+
+        encode(&Header::es256("foo"), &"bar", &signer).unwrap();
+        let signer_dyn: &dyn Signer = &signer;
+        encode(&Header::es256("foo"), &"bar", signer_dyn).unwrap();
+        assert_eq!(signer.count.load(Ordering::SeqCst), 2);
+
+        match encode(&Header::es256("foo"), &"bar", &UnavailableSigner) {
+            Err(crate::EncodeError::SignFailed(SignError::External(_))) => {}
+            x => panic!("{x:?}"),
+        }
+    }
+}
@@ -0,0 +1,293 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    claims::RegisteredClaims,
+    header::{Header, ALGORITHM_ES256, ALGORITHM_RS256},
+    inspect::AudienceMismatch,
+    jwk::JwkSet,
+    jwt::{decode_unverified, split, DecodeError},
+    key::KeyError,
+    public_key::EcPublicKey,
+};
+
+/// Checks the ES256 signature, then `aud`, `exp` and `iat`.
+pub fn verify<C: DeserializeOwned + RegisteredClaims>(
+    token: &str,
+    public_key: &EcPublicKey,
+    expected_audience: &'static str,
+) -> Result<(Header, C), VerifyError> {
+    let (header, claims) = decode_unverified::<C>(token).map_err(VerifyError::DecodeFailed)?;
+
+    if header.alg.as_ref() != ALGORITHM_ES256 {
+        return Err(VerifyError::AlgorithmUnsupported(header.alg));
+    }
+
+    let (signing_input, signature) =
+        signing_input_and_signature(token).map_err(VerifyError::DecodeFailed)?;
+    if !public_key.verify(signing_input.as_bytes(), &signature) {
+        return Err(VerifyError::SignatureInvalid);
+    }
+
+    if claims.aud() != expected_audience {
+        return Err(VerifyError::AudienceMismatch(AudienceMismatch {
+            expected: expected_audience,
+            actual: claims.aud().into(),
+        }));
+    }
+    let now = Utc::now();
+    if claims.exp() <= now {
+        return Err(VerifyError::Expired(claims.exp()));
+    }
+    if claims.iat() > now {
+        return Err(VerifyError::IssuedAtInFuture(claims.iat()));
+    }
+
+    Ok((header, claims))
+}
+
+/// Checks the RS256 signature with the key in `jwk_set` named by the header `kid`.
+///
+/// The claims are left to the caller.
+pub fn verify_rs256<C: DeserializeOwned>(
+    token: &str,
+    jwk_set: &JwkSet,
+) -> Result<(Header, C), Rs256VerifyError> {
+    let (header, claims) = decode_unverified::<C>(token).map_err(Rs256VerifyError::DecodeFailed)?;
+
+    if header.alg.as_ref() != ALGORITHM_RS256 {
+        return Err(Rs256VerifyError::AlgorithmUnsupported(header.alg));
+    }
+    let kid = header
+        .kid
+        .as_deref()
+        .ok_or(Rs256VerifyError::KeyIdMissing)?;
+    let public_key = jwk_set
+        .find(kid)
+        .ok_or_else(|| Rs256VerifyError::KeyNotFound(kid.into()))?
+        .to_public_key()
+        .map_err(Rs256VerifyError::KeyInvalid)?;
+
+    let (signing_input, signature) =
+        signing_input_and_signature(token).map_err(Rs256VerifyError::DecodeFailed)?;
+    if !public_key.verify_rs256(signing_input.as_bytes(), &signature) {
+        return Err(Rs256VerifyError::SignatureInvalid);
+    }
+
+    Ok((header, claims))
+}
+
+fn signing_input_and_signature(token: &str) -> Result<(&str, Vec<u8>), DecodeError> {
+    let (header_segment, claims_segment, signature_segment) = split(token)?;
+    let signature = URL_SAFE_NO_PAD
+        .decode(signature_segment)
+        .map_err(DecodeError::Base64DecodeFailed)?;
+
+    Ok((
+        &token[..header_segment.len() + 1 + claims_segment.len()],
+        signature,
+    ))
+}
+
+//
+#[derive(Debug)]
+pub enum VerifyError {
+    DecodeFailed(DecodeError),
+    AlgorithmUnsupported(Box<str>),
+    SignatureInvalid,
+    AudienceMismatch(AudienceMismatch),
+    Expired(DateTime<Utc>),
+    IssuedAtInFuture(DateTime<Utc>),
+}
+impl core::fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+impl std::error::Error for VerifyError {}
+
+//
+#[derive(Debug)]
+pub enum Rs256VerifyError {
+    DecodeFailed(DecodeError),
+    AlgorithmUnsupported(Box<str>),
+    KeyIdMissing,
+    KeyNotFound(Box<str>),
+    KeyInvalid(KeyError),
+    SignatureInvalid,
+}
+impl core::fmt::Display for Rs256VerifyError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+impl std::error::Error for Rs256VerifyError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use chrono::Duration as ChronoDuration;
+    use serde::{Deserialize, Serialize};
+
+    use crate::{
+        jwt::Encoder,
+        key::tests::{PKCS8_PEM, SEC1_PEM},
+        EcPrivateKey,
+    };
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    struct Claims {
+        #[serde(with = "chrono::serde::ts_seconds")]
+        iat: DateTime<Utc>,
+        #[serde(with = "chrono::serde::ts_seconds")]
+        exp: DateTime<Utc>,
+        aud: Box<str>,
+    }
+    impl RegisteredClaims for Claims {
+        fn aud(&self) -> &str {
+            &self.aud
+        }
+        fn iat(&self) -> DateTime<Utc> {
+            self.iat
+        }
+        fn exp(&self) -> DateTime<Utc> {
+            self.exp
+        }
+    }
+
+    /*
+    openssl ec -in private-key.pem -pubout -out public-key.pem
+    */
+    const PUBLIC_KEY_PEM: &str = r#"
+-----BEGIN PUBLIC KEY-----
+MFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAEY58v74eQFyLtu5rtCpeU4NggVSUQ
+SOcHhN744t0gWGc/xXkCSuszLaZriCQnnqq4Vx+IscLFcrjBj+ulZzKlUQ==
+-----END PUBLIC KEY-----
+    "#;
+
+    #[test]
+    fn test_verify() {
+        let key = EcPrivateKey::from_pem(PKCS8_PEM).unwrap();
+        let encoder = Encoder::new(Header::es256("foo"), Arc::new(key.clone())).unwrap();
+
+        let now = Utc::now();
+        let token = encoder
+            .encode(&Claims {
+                iat: now,
+                exp: now + ChronoDuration::minutes(20),
+                aud: "bar".into(),
+            })
+            .unwrap();
+
+        for public_key in [
+            key.public_key().unwrap(),
+            EcPrivateKey::from_pem(SEC1_PEM)
+                .unwrap()
+                .public_key()
+                .unwrap(),
+            EcPublicKey::from_pem(PUBLIC_KEY_PEM).unwrap(),
+        ] {
+            let (header, claims) = verify::<Claims>(&token, &public_key, "bar").unwrap();
+            assert_eq!(header.kid.as_deref(), Some("foo"));
+            assert_eq!(claims.aud.as_ref(), "bar");
+        }
+        let public_key = key.public_key().unwrap();
+
+        //
+        let tampered = format!("{}x", &token[..token.len() - 1]);
+        assert!(matches!(
+            verify::<Claims>(&tampered, &public_key, "bar"),
+            Err(VerifyError::SignatureInvalid) | Err(VerifyError::DecodeFailed(_))
+        ));
+        let (header_segment, rest) = token.split_once('.').unwrap();
+        let (_, signature_segment) = rest.split_once('.').unwrap();
+        let forged = format!(
+            "{header_segment}.{}.{signature_segment}",
+            URL_SAFE_NO_PAD.encode(format!(
+                r#"{{"iat":{},"exp":{},"aud":"bar"}}"#,
+                now.timestamp(),
+                now.timestamp() + 3600 * 24
+            ))
+        );
+        assert!(matches!(
+            verify::<Claims>(&forged, &public_key, "bar"),
+            Err(VerifyError::SignatureInvalid)
+        ));
+
+        //
+        assert!(matches!(
+            verify::<Claims>(&token, &public_key, "baz"),
+            Err(VerifyError::AudienceMismatch(_))
+        ));
+
+        let token = encoder
+            .encode(&Claims {
+                iat: now - ChronoDuration::minutes(30),
+                exp: now - ChronoDuration::minutes(10),
+                aud: "bar".into(),
+            })
+            .unwrap();
+        assert!(matches!(
+            verify::<Claims>(&token, &public_key, "bar"),
+            Err(VerifyError::Expired(_))
+        ));
+
+        let token = encoder
+            .encode(&Claims {
+                iat: now + ChronoDuration::minutes(10),
+                exp: now + ChronoDuration::minutes(30),
+                aud: "bar".into(),
+            })
+            .unwrap();
+        assert!(matches!(
+            verify::<Claims>(&token, &public_key, "bar"),
+            Err(VerifyError::IssuedAtInFuture(_))
+        ));
+    }
+
+    // The RSA test key, and an id token signed with it (`aud` com.example.app, `exp` 2100-01-01).
+    const JWK_SET_JSON: &str = include_str!("../tests/fixtures/apple-jwks.json");
+    const TOKEN: &str = include_str!("../tests/fixtures/apple-id-token.jwt");
+
+    #[test]
+    fn test_verify_rs256() {
+        #[derive(Deserialize)]
+        struct Claims {
+            aud: Box<str>,
+        }
+
+        let jwk_set = JwkSet::from_json(JWK_SET_JSON).unwrap();
+
+        let (header, claims) = verify_rs256::<Claims>(TOKEN, &jwk_set).unwrap();
+        assert_eq!(header.kid.as_deref(), Some("TEST_KID"));
+        assert_eq!(claims.aud.as_ref(), "com.example.app");
+
+        let tampered = format!("{}A", &TOKEN[..TOKEN.len() - 1]);
+        assert!(matches!(
+            verify_rs256::<Claims>(&tampered, &jwk_set),
+            Err(Rs256VerifyError::SignatureInvalid)
+        ));
+
+        assert!(matches!(
+            verify_rs256::<Claims>(TOKEN, &JwkSet::default()),
+            Err(Rs256VerifyError::KeyNotFound(kid)) if kid.as_ref() == "TEST_KID"
+        ));
+
+        // ES256 tokens are not accepted here
+        let token = Encoder::new(
+            Header::es256("TEST_KID"),
+            Arc::new(EcPrivateKey::from_pem(PKCS8_PEM).unwrap()),
+        )
+        .unwrap()
+        .encode(&serde_json::json!({"aud": "com.example.app"}))
+        .unwrap();
+        assert!(matches!(
+            verify_rs256::<Claims>(&token, &jwk_set),
+            Err(Rs256VerifyError::AlgorithmUnsupported(_))
+        ));
+    }
+}
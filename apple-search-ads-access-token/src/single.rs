@@ -2,7 +2,8 @@ use core::{future::Future, pin::Pin, time::Duration};
 use std::{sync::Arc, time::SystemTime};
 
 use apple_search_ads_client_secret::{
-    create as client_secret_create, CreateError as ClientSecretCreateError,
+    apple_jwt_core::Signer, create as client_secret_create,
+    create_with_signer as client_secret_create_with_signer, CreateError as ClientSecretCreateError,
 };
 use arc_swap::ArcSwap;
 use async_sleep::{sleep, timeout, Sleepble};
@@ -50,19 +51,61 @@ impl Manager {
         ec_private_key_pem_bytes: impl AsRef<[u8]>,
         team_id: impl AsRef<str>,
         client_id: impl AsRef<str>,
+    ) -> Result<(ResponseSuccessfulBody, IssuedAt), ManagerRequestError> {
+        self.request_inner(
+            key_id.as_ref(),
+            ClientSecretKey::Pem(ec_private_key_pem_bytes.as_ref()),
+            team_id.as_ref(),
+            client_id.as_ref(),
+        )
+        .await
+    }
+
+    pub async fn request_with_signer(
+        &self,
+        key_id: impl AsRef<str>,
+        signer: &dyn Signer,
+        team_id: impl AsRef<str>,
+        client_id: impl AsRef<str>,
+    ) -> Result<(ResponseSuccessfulBody, IssuedAt), ManagerRequestError> {
+        self.request_inner(
+            key_id.as_ref(),
+            ClientSecretKey::Signer(signer),
+            team_id.as_ref(),
+            client_id.as_ref(),
+        )
+        .await
+    }
+
+    async fn request_inner(
+        &self,
+        key_id: &str,
+        key: ClientSecretKey<'_>,
+        team_id: &str,
+        client_id: &str,
     ) -> Result<(ResponseSuccessfulBody, IssuedAt), ManagerRequestError> {
         let client_secret = match get_not_expired_client_secret() {
             Some(x) => x,
             None => {
                 let issued_at = SystemTime::now();
-                let client_secret = client_secret_create(
-                    key_id,
-                    ec_private_key_pem_bytes,
-                    team_id,
-                    client_id.as_ref(),
-                    None,
-                    CLIENT_SECRET_EXP_DUR,
-                )
+                let client_secret = match key {
+                    ClientSecretKey::Pem(ec_private_key_pem_bytes) => client_secret_create(
+                        key_id,
+                        ec_private_key_pem_bytes,
+                        team_id,
+                        client_id,
+                        None,
+                        CLIENT_SECRET_EXP_DUR,
+                    ),
+                    ClientSecretKey::Signer(signer) => client_secret_create_with_signer(
+                        key_id,
+                        signer,
+                        team_id,
+                        client_id,
+                        None,
+                        CLIENT_SECRET_EXP_DUR,
+                    ),
+                }
                 .map_err(ManagerRequestError::ClientSecretCreateFailed)?;
 
                 let storage = ClientSecretStorage(Some((client_secret.to_owned(), issued_at)));
@@ -73,11 +116,11 @@ impl Manager {
         };
 
         let flow = Flow::new(ACCESS_TOKEN_REQUEST_HTTP_CLIENT.to_owned());
-        let provider = AppleProviderForSearchAdsApi::new(
-            client_id.as_ref().to_string(),
-            client_secret.as_ref().to_string(),
-        )
-        .map_err(|err| ManagerRequestError::OauthProviderMakeFailed(err.to_string().into()))?;
+        let provider =
+            AppleProviderForSearchAdsApi::new(client_id.to_string(), client_secret.to_string())
+                .map_err(|err| {
+                    ManagerRequestError::OauthProviderMakeFailed(err.to_string().into())
+                })?;
 
         let issued_at = SystemTime::now();
         let body = flow
@@ -105,6 +148,56 @@ impl Manager {
             ) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>>
             + Send
             + Sync,
+    {
+        self.run_inner::<SLEEP, _>(
+            key_id,
+            ClientSecretKey::Pem(ec_private_key_pem_bytes),
+            team_id,
+            client_id,
+            request_callback,
+        )
+        .await
+    }
+
+    pub async fn run_with_signer<SLEEP, RequestCb>(
+        &self,
+        key_id: &str,
+        signer: &dyn Signer,
+        team_id: &str,
+        client_id: &str,
+        request_callback: RequestCb,
+    ) where
+        SLEEP: Sleepble,
+        RequestCb: Fn(
+                Result<(ResponseSuccessfulBody, IssuedAt), ManagerRequestError>,
+            ) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>>
+            + Send
+            + Sync,
+    {
+        self.run_inner::<SLEEP, _>(
+            key_id,
+            ClientSecretKey::Signer(signer),
+            team_id,
+            client_id,
+            request_callback,
+        )
+        .await
+    }
+
+    async fn run_inner<SLEEP, RequestCb>(
+        &self,
+        key_id: &str,
+        key: ClientSecretKey<'_>,
+        team_id: &str,
+        client_id: &str,
+        request_callback: RequestCb,
+    ) where
+        SLEEP: Sleepble,
+        RequestCb: Fn(
+                Result<(ResponseSuccessfulBody, IssuedAt), ManagerRequestError>,
+            ) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>>
+            + Send
+            + Sync,
     {
         loop {
             if get_not_expired_access_token().is_some() {
@@ -112,15 +205,7 @@ impl Manager {
                 continue;
             }
 
-            match self
-                .request(
-                    key_id,
-                    ec_private_key_pem_bytes.as_ref(),
-                    team_id,
-                    client_id,
-                )
-                .await
-            {
+            match self.request_inner(key_id, key, team_id, client_id).await {
                 Ok((body, issued_at)) => {
                     let _ = timeout::<SLEEP, _>(
                         Duration::from_secs(6),
@@ -143,6 +228,13 @@ impl Manager {
     }
 }
 
+//
+#[derive(Clone, Copy)]
+enum ClientSecretKey<'a> {
+    Pem(&'a [u8]),
+    Signer(&'a dyn Signer),
+}
+
 //
 #[derive(Debug)]
 pub enum ManagerRequestError {
@@ -1,27 +1,58 @@
 /*
 cargo run -p apple-search-ads-client-secret-cli --bin apple_search_ads_client_secret_gen -- 'key_id' '/path/private-key.pem' 'team_id' 'client_id'
+cargo run -p apple-search-ads-client-secret-cli --bin apple_search_ads_client_secret_gen -- public-key '/path/private-key.pem' ['key_id']
 
 Or
 
 cargo install apple-search-ads-client-secret-cli
 apple_search_ads_client_secret_gen 'key_id' '/path/private-key.pem' 'team_id' 'client_id'
+apple_search_ads_client_secret_gen public-key '/path/private-key.pem' ['key_id']
+apple_search_ads_client_secret_gen public-key 'file:/path/private-key.der' ['key_id']
+
+The key may also be given as `env:NAME`, `base64:MHcC...`, `file:/path/private-key.der` or inline PEM.
+Encrypted PKCS#8 keys are decrypted with the passphrase in `APPLE_PRIVATE_KEY_PASSPHRASE`, or prompted for.
 */
 
-use std::{env, fs};
+use std::env;
 
-use apple_search_ads_client_secret::create;
+use apple_search_ads_client_secret::{apple_jwt_core::KeySource, create_with_signer};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    if env::args().nth(1).as_deref() == Some("public-key") {
+        return print_public_key();
+    }
+
     let key_id = env::args().nth(1).unwrap();
-    let private_key_path = env::args().nth(2).unwrap();
+    let private_key_source = env::args().nth(2).unwrap().parse::<KeySource>()?;
     let team_id = env::args().nth(3).unwrap();
     let client_id = env::args().nth(4).unwrap();
 
-    let private_key_bytes = fs::read(private_key_path)?;
+    let private_key = private_key_source
+        .load_with_passphrase_prompt(|| rpassword::prompt_password("Passphrase: "))?;
 
-    let client_secret = create(key_id, private_key_bytes, team_id, client_id, None, None)?;
+    let client_secret = create_with_signer(key_id, &private_key, team_id, client_id, None, None)?;
 
     println!("{client_secret}");
 
     Ok(())
 }
+
+fn print_public_key() -> Result<(), Box<dyn std::error::Error>> {
+    let private_key_source = env::args().nth(2).unwrap().parse::<KeySource>()?;
+    let key_id = env::args().nth(3);
+
+    let public_key = private_key_source
+        .load_with_passphrase_prompt(|| rpassword::prompt_password("Passphrase: "))?
+        .public_key()?;
+
+    let mut jwk = public_key.to_jwk()?;
+    if let Some(key_id) = key_id {
+        jwk = jwk.with_kid(key_id);
+    }
+
+    print!("{}", public_key.to_pem()?);
+    println!("{jwk}");
+    println!("{}", jwk.thumbprint());
+
+    Ok(())
+}
@@ -5,21 +5,25 @@ Or
 
 cargo install apple-siwa-client-secret-cli
 apple_siwa_client_secret_gen 'key_id' '/path/AuthKey_xxx.p8' 'team_id' 'client_id'
+
+The key may also be given as `env:NAME`, `base64:MIGH...`, `file:/path/AuthKey_xxx.der` or inline PEM.
+Encrypted PKCS#8 keys are decrypted with the passphrase in `APPLE_PRIVATE_KEY_PASSPHRASE`, or prompted for.
 */
 
-use std::{env, fs};
+use std::env;
 
-use apple_siwa_client_secret::create;
+use apple_siwa_client_secret::{apple_jwt_core::KeySource, create_with_signer};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let key_id = env::args().nth(1).unwrap();
-    let auth_key_path = env::args().nth(2).unwrap();
+    let auth_key_source = env::args().nth(2).unwrap().parse::<KeySource>()?;
     let team_id = env::args().nth(3).unwrap();
     let client_id = env::args().nth(4).unwrap();
 
-    let auth_key_bytes = fs::read(auth_key_path)?;
+    let auth_key = auth_key_source
+        .load_with_passphrase_prompt(|| rpassword::prompt_password("Passphrase: "))?;
 
-    let client_secret = create(key_id, auth_key_bytes, team_id, client_id, None, None)?;
+    let client_secret = create_with_signer(key_id, &auth_key, team_id, client_id, None, None)?;
 
     println!("{client_secret}");
 
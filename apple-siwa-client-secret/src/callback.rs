@@ -0,0 +1,212 @@
+//! [Doc](https://developer.apple.com/documentation/sign_in_with_apple/sign_in_with_apple_js/incorporating_sign_in_with_apple_into_other_platforms)
+//!
+//! The `response_mode=form_post` callback, an `application/x-www-form-urlencoded` POST.
+
+use apple_jwt_core::JwkSet;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    id_token::{Claims, IdTokenError, Verifier},
+    nonce,
+};
+
+//
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Callback {
+    pub code: Option<Box<str>>,
+    pub id_token: Option<Box<str>>,
+    pub state: Option<Box<str>>,
+    /// Only sent on the first sign in, keep it.
+    pub user: Option<User>,
+    /// The `user` JSON as sent, also kept when it does not parse into [`User`].
+    pub user_raw: Option<Box<str>>,
+    /// e.g. `user_cancelled_authorize`
+    pub error: Option<Box<str>>,
+}
+
+//
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct User {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<UserName>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<Box<str>>,
+}
+
+//
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct UserName {
+    #[serde(rename = "firstName", default, skip_serializing_if = "Option::is_none")]
+    pub first_name: Option<Box<str>>,
+    #[serde(rename = "lastName", default, skip_serializing_if = "Option::is_none")]
+    pub last_name: Option<Box<str>>,
+}
+
+//
+#[derive(Debug, Clone)]
+pub struct VerifiedCallback {
+    pub code: Box<str>,
+    pub claims: Claims,
+    pub user: Option<User>,
+}
+
+impl Callback {
+    /// Parses the request body. The first occurrence of a field wins.
+    pub fn from_form(body: impl AsRef<[u8]>) -> Self {
+        let mut callback = Self::default();
+
+        for (k, v) in form_urlencoded::parse(body.as_ref()) {
+            let field = match k.as_ref() {
+                "code" => &mut callback.code,
+                "id_token" => &mut callback.id_token,
+                "state" => &mut callback.state,
+                "error" => &mut callback.error,
+                "user" => &mut callback.user_raw,
+                _ => continue,
+            };
+            if field.is_none() {
+                *field = Some(v.as_ref().into());
+            }
+        }
+
+        callback.user = callback
+            .user_raw
+            .as_deref()
+            .and_then(|user| serde_json::from_str(user).ok());
+
+        callback
+    }
+
+    pub fn check_state(&self, expected_state: impl AsRef<str>) -> Result<(), CallbackError> {
+        match self.state.as_deref() {
+            Some(state) if nonce::eq(state, expected_state.as_ref()) => Ok(()),
+            Some(_) => Err(CallbackError::StateMismatch),
+            None => Err(CallbackError::StateMissing),
+        }
+    }
+
+    /// Checks `error`, then `state`, then verifies the `id_token`.
+    pub fn verify(
+        &self,
+        expected_state: impl AsRef<str>,
+        verifier: &Verifier,
+        jwk_set: &JwkSet,
+        nonce: Option<&str>,
+    ) -> Result<VerifiedCallback, CallbackError> {
+        if let Some(error) = &self.error {
+            return Err(CallbackError::ErrorReturned(error.to_owned()));
+        }
+        self.check_state(expected_state)?;
+
+        let id_token = self
+            .id_token
+            .as_deref()
+            .ok_or(CallbackError::IdTokenMissing)?;
+        let code = self.code.to_owned().ok_or(CallbackError::CodeMissing)?;
+        let claims = verifier
+            .verify(id_token, jwk_set, nonce)
+            .map_err(CallbackError::IdTokenInvalid)?;
+
+        Ok(VerifiedCallback {
+            code,
+            claims,
+            user: self.user.to_owned(),
+        })
+    }
+}
+
+//
+#[derive(Debug)]
+pub enum CallbackError {
+    ErrorReturned(Box<str>),
+    StateMissing,
+    StateMismatch,
+    CodeMissing,
+    IdTokenMissing,
+    IdTokenInvalid(IdTokenError),
+}
+impl core::fmt::Display for CallbackError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+impl std::error::Error for CallbackError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::id_token::tests::{ID_TOKEN, JWK_SET_JSON};
+
+    #[test]
+    fn test_from_form_and_verify() {
+        let jwk_set = JwkSet::from_json(JWK_SET_JSON).unwrap();
+        let verifier = Verifier::new(["com.example.app"]);
+
+        let body = form_urlencoded::Serializer::new(String::new())
+            .append_pair("state", "state-1")
+            .append_pair("code", "c.0.abc")
+            .append_pair("id_token", ID_TOKEN)
+            .append_pair(
+                "user",
+                r#"{"name":{"firstName":"John","lastName":"Appleseed"},"email":"foo@privaterelay.appleid.com"}"#,
+            )
+            .finish();
+
+        let callback = Callback::from_form(&body);
+        assert_eq!(
+            callback.user,
+            Some(User {
+                name: Some(UserName {
+                    first_name: Some("John".into()),
+                    last_name: Some("Appleseed".into()),
+                }),
+                email: Some("foo@privaterelay.appleid.com".into()),
+            })
+        );
+
+        match callback.verify("state-2", &verifier, &jwk_set, None) {
+            Err(CallbackError::StateMismatch) => {}
+            x => panic!("{x:?}"),
+        }
+        // still there to retry with, or to store
+        assert!(callback.user.is_some());
+
+        let verified = callback
+            .verify("state-1", &verifier, &jwk_set, Some("nonce-1"))
+            .unwrap();
+        assert_eq!(verified.code.as_ref(), "c.0.abc");
+        assert_eq!(
+            verified.claims.sub.as_ref(),
+            "001234.0123456789abcdef0123456789abcdef.1234"
+        );
+        assert!(verified.user.is_some());
+
+        // Later sign ins have no `user`.
+        let callback =
+            Callback::from_form(format!("state=state-1&code=c.0.abc&id_token={ID_TOKEN}"));
+        assert!(callback.user.is_none());
+        callback
+            .verify("state-1", &verifier, &jwk_set, None)
+            .unwrap();
+
+        let callback = Callback::from_form("error=user_cancelled_authorize&state=state-1");
+        match callback.verify("state-1", &verifier, &jwk_set, None) {
+            Err(CallbackError::ErrorReturned(err)) => {
+                assert_eq!(err.as_ref(), "user_cancelled_authorize")
+            }
+            x => panic!("{x:?}"),
+        }
+
+        match Callback::from_form("code=c.0.abc&id_token=x.y.z").check_state("state-1") {
+            Err(CallbackError::StateMissing) => {}
+            x => panic!("{x:?}"),
+        }
+
+        // A malformed `user` keeps the rest, and the raw JSON.
+        let callback = Callback::from_form("state=state-1&code=c.0.abc&user=%7B");
+        assert!(callback.user.is_none());
+        assert_eq!(callback.user_raw.as_deref(), Some("{"));
+        assert_eq!(callback.code.as_deref(), Some("c.0.abc"));
+    }
+}
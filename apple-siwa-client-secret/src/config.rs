@@ -0,0 +1,139 @@
+//! One team and key, several client ids, e.g. an iOS and a macOS bundle id plus a web Services ID.
+
+use std::sync::Arc;
+
+use apple_jwt_core::JwkSet;
+
+use crate::{
+    id_token::{Claims, IdTokenError, Verifier},
+    CreateError, Generator, Manager,
+};
+
+//
+#[derive(Debug, Clone)]
+pub struct Config {
+    manager: Arc<Manager>,
+    verifier: Verifier,
+}
+
+impl Config {
+    pub fn new<T: AsRef<str>>(
+        generator: Generator,
+        client_ids: impl IntoIterator<Item = T>,
+    ) -> Self {
+        Self::with_manager(Arc::new(Manager::new(generator)), client_ids)
+    }
+
+    /// Shares `manager`, e.g. one whose client secrets were persisted and loaded back.
+    pub fn with_manager<T: AsRef<str>>(
+        manager: Arc<Manager>,
+        client_ids: impl IntoIterator<Item = T>,
+    ) -> Self {
+        Self {
+            manager,
+            verifier: Verifier::new(client_ids),
+        }
+    }
+
+    /// Clock skew tolerated on `exp` and `iat` of id tokens.
+    pub fn with_leeway(mut self, leeway: core::time::Duration) -> Self {
+        self.verifier = self.verifier.with_leeway(leeway);
+        self
+    }
+
+    pub fn client_ids(&self) -> &[Box<str>] {
+        self.verifier.client_ids()
+    }
+
+    pub fn contains(&self, client_id: impl AsRef<str>) -> bool {
+        self.client_ids()
+            .iter()
+            .any(|x| x.as_ref() == client_id.as_ref())
+    }
+
+    pub fn manager(&self) -> &Arc<Manager> {
+        &self.manager
+    }
+
+    /// Accepts any of the client ids as `aud`.
+    pub fn verifier(&self) -> &Verifier {
+        &self.verifier
+    }
+
+    /// The cached client secret for `client_id`, re-minted first when it is missing or about to expire.
+    pub fn client_secret(&self, client_id: impl AsRef<str>) -> Result<Box<str>, ConfigError> {
+        if !self.contains(client_id.as_ref()) {
+            return Err(ConfigError::ClientIdUnknown(client_id.as_ref().into()));
+        }
+
+        self.manager
+            .get_value(client_id)
+            .map_err(ConfigError::ClientSecretCreateFailed)
+    }
+
+    pub fn verify_id_token(
+        &self,
+        id_token: impl AsRef<str>,
+        jwk_set: &JwkSet,
+        nonce: Option<&str>,
+    ) -> Result<Claims, IdTokenError> {
+        self.verifier.verify(id_token, jwk_set, nonce)
+    }
+}
+
+//
+#[derive(Debug)]
+pub enum ConfigError {
+    ClientIdUnknown(Box<str>),
+    ClientSecretCreateFailed(CreateError),
+}
+impl core::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+impl std::error::Error for ConfigError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        id_token::tests::{ID_TOKEN, JWK_SET_JSON},
+        inspect,
+        tests::generator,
+    };
+
+    #[test]
+    fn test_config() {
+        let config = Config::new(
+            generator(),
+            ["com.example.app", "com.example.mac", "com.example.web"],
+        );
+        assert!(config.contains("com.example.mac"));
+
+        let app = config.client_secret("com.example.app").unwrap();
+        let web = config.client_secret("com.example.web").unwrap();
+        assert_eq!(config.client_secret("com.example.app").unwrap(), app);
+        assert_eq!(inspect(&app).unwrap().claims.sub, "com.example.app");
+        assert_eq!(inspect(&web).unwrap().claims.sub, "com.example.web");
+        assert_eq!(config.manager().client_secrets().len(), 2);
+
+        match config.client_secret("com.example.other") {
+            Err(ConfigError::ClientIdUnknown(client_id)) => {
+                assert_eq!(client_id.as_ref(), "com.example.other")
+            }
+            x => panic!("{x:?}"),
+        }
+
+        // ID_TOKEN's aud is com.example.app.
+        let jwk_set = JwkSet::from_json(JWK_SET_JSON).unwrap();
+        config.verify_id_token(ID_TOKEN, &jwk_set, None).unwrap();
+        match Config::new(generator(), ["com.example.mac", "com.example.web"])
+            .verify_id_token(ID_TOKEN, &jwk_set, None)
+        {
+            Err(IdTokenError::AudienceMismatch(_)) => {}
+            x => panic!("{x:?}"),
+        }
+    }
+}
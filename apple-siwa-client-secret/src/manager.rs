@@ -0,0 +1,279 @@
+use core::time::Duration;
+use std::{collections::HashMap, sync::Arc};
+
+use apple_jwt_core::DecodeError;
+use arc_swap::ArcSwap;
+use chrono::{serde::ts_seconds, DateTime, Duration as ChronoDuration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{inspect, CreateError, Generator, EXPIRATION_TIME_DURATION_SECONDS_MAX};
+
+//
+const REFRESH_AHEAD_DUR: Duration = Duration::from_secs(60 * 60 * 24);
+
+/// Serializable, so it can be persisted and loaded back with [`Manager::set`].
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ClientSecret {
+    pub client_id: Box<str>,
+    pub value: Box<str>,
+    #[serde(with = "ts_seconds")]
+    pub issued_at: DateTime<Utc>,
+    #[serde(with = "ts_seconds")]
+    pub expires_at: DateTime<Utc>,
+}
+
+impl ClientSecret {
+    /// Reads `sub`, `iat` and `exp` back from a persisted client secret, without checking the signature.
+    ///
+    /// [`Manager::set`] checks the rest against its generator.
+    pub fn from_value(value: impl AsRef<str>) -> Result<Self, DecodeError> {
+        let claims = inspect(value.as_ref())?.claims;
+
+        Ok(Self {
+            client_id: claims.sub.into(),
+            value: value.as_ref().into(),
+            issued_at: claims.iat,
+            expires_at: claims.exp,
+        })
+    }
+}
+
+impl core::fmt::Debug for ClientSecret {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ClientSecret")
+            .field("client_id", &self.client_id)
+            .field("issued_at", &self.issued_at)
+            .field("expires_at", &self.expires_at)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Caches a client secret per client id and re-mints it `refresh_ahead_dur` before it expires.
+pub struct Manager {
+    generator: Generator,
+    expiration_time_dur: Duration,
+    refresh_ahead_dur: Duration,
+    storage: ArcSwap<HashMap<Box<str>, Arc<ClientSecret>>>,
+}
+
+impl Manager {
+    pub fn new(generator: Generator) -> Self {
+        Self {
+            generator,
+            expiration_time_dur: Duration::from_secs(EXPIRATION_TIME_DURATION_SECONDS_MAX),
+            refresh_ahead_dur: REFRESH_AHEAD_DUR,
+            storage: ArcSwap::from_pointee(HashMap::new()),
+        }
+    }
+
+    pub fn with_expiration_time_dur(mut self, dur: Duration) -> Self {
+        self.expiration_time_dur = dur;
+        self
+    }
+
+    /// Capped at half the lifetime of each client secret, so a large value cannot make every call re-mint.
+    pub fn with_refresh_ahead_dur(mut self, dur: Duration) -> Self {
+        self.refresh_ahead_dur = dur;
+        self
+    }
+
+    /// Ignored, returning `false`, unless `kid`, `iss`, `aud` and `sub` match this manager's
+    /// generator, e.g. a persisted client secret from before a key rotation.
+    ///
+    /// Also ignored when `issued_at`/`expires_at` differ from the value's `iat`/`exp`, or it has expired.
+    pub fn set(&self, client_secret: ClientSecret) -> bool {
+        let matched = inspect(client_secret.value.as_ref()).is_ok_and(|inspection| {
+            inspection.header.kid.as_deref() == self.generator.key_id()
+                && inspection.claims.iss == self.generator.team_id()
+                && inspection.audience_mismatch().is_none()
+                && inspection.claims.sub == client_secret.client_id.as_ref()
+                && inspection.claims.iat == client_secret.issued_at
+                && inspection.claims.exp == client_secret.expires_at
+                && !inspection.is_expired()
+        });
+        if matched {
+            self.store(client_secret);
+        }
+        matched
+    }
+
+    fn store(&self, client_secret: ClientSecret) {
+        let client_secret = Arc::new(client_secret);
+        self.storage.rcu(|storage| {
+            let mut storage = HashMap::clone(storage);
+            storage.insert(client_secret.client_id.to_owned(), client_secret.clone());
+            storage
+        });
+    }
+
+    pub fn remove(&self, client_id: impl AsRef<str>) {
+        self.storage.rcu(|storage| {
+            let mut storage = HashMap::clone(storage);
+            storage.remove(client_id.as_ref());
+            storage
+        });
+    }
+
+    pub fn clear(&self) {
+        self.storage.store(Arc::new(HashMap::new()));
+    }
+
+    /// Every cached client secret, e.g. to persist them.
+    pub fn client_secrets(&self) -> Vec<ClientSecret> {
+        self.storage
+            .load()
+            .values()
+            .map(|client_secret| ClientSecret::clone(client_secret))
+            .collect()
+    }
+
+    /// The cached client secret, if it is not about to expire.
+    pub fn get(&self, client_id: impl AsRef<str>) -> Option<Arc<ClientSecret>> {
+        let refresh_ahead_dur = ChronoDuration::seconds(self.refresh_ahead_dur.as_secs() as i64);
+
+        self.storage
+            .load()
+            .get(client_id.as_ref())
+            .filter(|client_secret| {
+                let refresh_ahead_dur =
+                    refresh_ahead_dur.min((client_secret.expires_at - client_secret.issued_at) / 2);
+                Utc::now() + refresh_ahead_dur < client_secret.expires_at
+            })
+            .cloned()
+    }
+
+    /// The cached client secret value, re-minted first when it is missing or about to expire.
+    pub fn get_value(&self, client_id: impl AsRef<str>) -> Result<Box<str>, CreateError> {
+        match self.get(client_id.as_ref()) {
+            Some(client_secret) => Ok(client_secret.value.to_owned()),
+            None => self
+                .refresh(client_id)
+                .map(|client_secret| client_secret.value.to_owned()),
+        }
+    }
+
+    pub fn refresh(&self, client_id: impl AsRef<str>) -> Result<Arc<ClientSecret>, CreateError> {
+        let claims = self
+            .generator
+            .claims(client_id, None, self.expiration_time_dur)?;
+        let value = self.generator.encode(&claims)?;
+
+        let client_secret = ClientSecret {
+            client_id: claims.sub.into(),
+            value: value.into(),
+            issued_at: claims.iat,
+            expires_at: claims.exp,
+        };
+        self.store(client_secret.clone());
+
+        Ok(Arc::new(client_secret))
+    }
+}
+
+/// Shows the client ids only, never the cached client secrets.
+impl core::fmt::Debug for Manager {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Manager")
+            .field("generator", &self.generator)
+            .field("expiration_time_dur", &self.expiration_time_dur)
+            .field("refresh_ahead_dur", &self.refresh_ahead_dur)
+            .field(
+                "client_ids",
+                &self.storage.load().keys().collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::tests::{generator, KEY_ID, P8_PRIVATE_KEY, TEAM_ID};
+
+    #[test]
+    fn test_get_value() {
+        let manager = Manager::new(generator());
+
+        assert!(manager.get("com.example.app").is_none());
+        let value = manager.get_value("com.example.app").unwrap();
+        assert_eq!(manager.get_value("com.example.app").unwrap(), value);
+        assert_ne!(manager.get_value("com.example.web").unwrap(), value);
+        let client_secret = manager.get("com.example.app").unwrap();
+        assert_eq!(
+            (client_secret.expires_at - client_secret.issued_at).num_seconds() as u64,
+            EXPIRATION_TIME_DURATION_SECONDS_MAX
+        );
+
+        // persist, then load into another manager
+        let json = serde_json::to_string(&manager.client_secrets()).unwrap();
+        let manager = Manager::new(generator());
+        for client_secret in serde_json::from_str::<Vec<ClientSecret>>(&json).unwrap() {
+            assert!(manager.set(client_secret));
+        }
+        assert_eq!(manager.get_value("com.example.app").unwrap(), value);
+        assert_eq!(
+            ClientSecret::from_value(&value).unwrap(),
+            *manager.get("com.example.app").unwrap()
+        );
+
+        // minted with another key id or team id
+        for generator in [
+            Generator::new("ABC1234567", P8_PRIVATE_KEY, TEAM_ID).unwrap(),
+            Generator::new(KEY_ID, P8_PRIVATE_KEY, "FGHIJ67890").unwrap(),
+        ] {
+            let value = generator.create("com.example.app", None, None).unwrap();
+            assert!(!manager.set(ClientSecret::from_value(value).unwrap()));
+        }
+        assert!(!manager.set(ClientSecret {
+            client_id: "com.example.web".into(),
+            ..ClientSecret::from_value(&value).unwrap()
+        }));
+        assert!(!manager.set(ClientSecret {
+            expires_at: Utc::now() + ChronoDuration::days(365),
+            ..ClientSecret::from_value(&value).unwrap()
+        }));
+        assert!(!manager.set(ClientSecret {
+            issued_at: Utc::now(),
+            ..ClientSecret::from_value(&value).unwrap()
+        }));
+        // already expired
+        let expired = generator()
+            .create(
+                "com.example.app",
+                "2022-06-06T00:00:00Z".parse::<DateTime<Utc>>().unwrap(),
+                None,
+            )
+            .unwrap();
+        assert!(!manager.set(ClientSecret::from_value(expired).unwrap()));
+        assert_eq!(manager.get_value("com.example.app").unwrap(), value);
+        assert!(!format!("{manager:?}").contains(value.as_ref()));
+
+        // about to expire
+        manager.store(ClientSecret {
+            client_id: "com.example.app".into(),
+            value: "foo".into(),
+            issued_at: Utc::now() - ChronoDuration::days(180),
+            expires_at: Utc::now() + ChronoDuration::hours(2),
+        });
+        assert_ne!(
+            manager.get_value("com.example.app").unwrap().as_ref(),
+            "foo"
+        );
+
+        manager.remove("com.example.app");
+        assert!(manager.get("com.example.app").is_none());
+        manager.clear();
+        assert!(manager.client_secrets().is_empty());
+    }
+
+    #[test]
+    fn test_with_refresh_ahead_dur() {
+        let manager = Manager::new(generator())
+            .with_expiration_time_dur(Duration::from_secs(60 * 60))
+            .with_refresh_ahead_dur(Duration::from_secs(60 * 60 * 24));
+
+        let value = manager.get_value("com.example.app").unwrap();
+        assert_eq!(manager.get_value("com.example.app").unwrap(), value);
+    }
+}
@@ -0,0 +1,68 @@
+//! The app sends `hash(raw_nonce)` to Apple, which puts it in the id token's `nonce` claim.
+//! The backend keeps the raw nonce and checks it with [`verify`], or passes it to
+//! [`Verifier::verify_with_raw_nonce`](crate::id_token::Verifier::verify_with_raw_nonce).
+
+use core::fmt::Write as _;
+
+use apple_jwt_core::{
+    crypto::{fill_random, sha256},
+    BackendError,
+};
+use subtle::ConstantTimeEq as _;
+
+pub const RAW_NONCE_BYTES_LEN: usize = 32;
+
+/// 32 random bytes, hex encoded.
+pub fn generate() -> Result<Box<str>, BackendError> {
+    let mut bytes = [0; RAW_NONCE_BYTES_LEN];
+    fill_random(&mut bytes)?;
+
+    Ok(to_hex(&bytes))
+}
+
+/// Lowercase hex of the SHA-256 digest, as done by the Apple samples.
+pub fn hash(raw_nonce: impl AsRef<str>) -> Box<str> {
+    to_hex(&sha256(raw_nonce.as_ref()))
+}
+
+/// Compares `hash(raw_nonce)` with the `nonce` claim in constant time.
+pub fn verify(raw_nonce: impl AsRef<str>, nonce: impl AsRef<str>) -> bool {
+    eq(hash(raw_nonce), nonce)
+}
+
+/// Constant time, apart from the lengths.
+pub fn eq(a: impl AsRef<str>, b: impl AsRef<str>) -> bool {
+    a.as_ref().as_bytes().ct_eq(b.as_ref().as_bytes()).into()
+}
+
+fn to_hex(bytes: &[u8]) -> Box<str> {
+    let mut s = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        let _ = write!(s, "{b:02x}");
+    }
+    s.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nonce() {
+        let raw_nonce = generate().unwrap();
+        assert_eq!(raw_nonce.len(), RAW_NONCE_BYTES_LEN * 2);
+        assert_ne!(generate().unwrap(), raw_nonce);
+
+        // printf foo | sha256sum
+        assert_eq!(
+            hash("foo").as_ref(),
+            "2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae"
+        );
+        assert!(verify(
+            "foo",
+            "2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae"
+        ));
+        assert!(!verify("foo", "foo"));
+        assert!(!eq("foo", "fo"));
+    }
+}
@@ -0,0 +1,732 @@
+use std::sync::Arc;
+
+use apple_siwa_client_secret::{
+    apple_jwt_core::JwkSet,
+    id_token::{Claims as IdTokenClaims, IdTokenError, Verifier as IdTokenVerifier},
+    Config, ConfigError, CreateError, Generator, Manager as ClientSecretManager,
+};
+use http_api_client::{Client as HttpClient, ClientRespondEndpointError};
+
+use crate::{
+    endpoints::{
+        revoke::{RevokeEndpoint, TokenTypeHint, URL as REVOKE_URL},
+        token::{Grant, TokenEndpoint, TokenResponseBody, URL as TOKEN_URL},
+        user_migration_info::{
+            UserMigrationInfoEndpoint, UserMigrationInfoRequest, UserMigrationInfoResponseBody,
+            SCOPE as USER_MIGRATION_SCOPE, URL as USER_MIGRATION_INFO_URL,
+        },
+        EndpointError,
+    },
+    oauth_error::{OAuthError, OAuthErrorCode},
+};
+
+/// Talks to the Sign in with Apple REST API as one `client_id`, minting and caching the client secret.
+#[derive(Debug)]
+pub struct Client<C> {
+    http_client: C,
+    client_secret_manager: Arc<ClientSecretManager>,
+    client_id: Box<str>,
+    token_url: Box<str>,
+    revoke_url: Box<str>,
+    user_migration_info_url: Box<str>,
+    id_token_verifier: IdTokenVerifier,
+}
+
+impl<C> Client<C> {
+    pub fn new(http_client: C, generator: Generator, client_id: impl AsRef<str>) -> Self {
+        Self::with_client_secret_manager(
+            http_client,
+            Arc::new(ClientSecretManager::new(generator)),
+            client_id,
+        )
+    }
+
+    /// Shares `client_secret_manager`, e.g. one whose client secrets were persisted and loaded back.
+    pub fn with_client_secret_manager(
+        http_client: C,
+        client_secret_manager: Arc<ClientSecretManager>,
+        client_id: impl AsRef<str>,
+    ) -> Self {
+        Self {
+            http_client,
+            client_secret_manager,
+            client_id: client_id.as_ref().into(),
+            token_url: TOKEN_URL.into(),
+            revoke_url: REVOKE_URL.into(),
+            user_migration_info_url: USER_MIGRATION_INFO_URL.into(),
+            id_token_verifier: IdTokenVerifier::new([client_id.as_ref()]),
+        }
+    }
+
+    /// Shares the client secrets and leeway of `config`. Token endpoint responses are for
+    /// `client_id` alone, so only it is accepted as `aud`, unlike [`Config::verify_id_token`].
+    pub fn with_config(
+        http_client: C,
+        config: &Config,
+        client_id: impl AsRef<str>,
+    ) -> Result<Self, ConfigError> {
+        if !config.contains(client_id.as_ref()) {
+            return Err(ConfigError::ClientIdUnknown(client_id.as_ref().into()));
+        }
+
+        let id_token_verifier =
+            IdTokenVerifier::new([client_id.as_ref()]).with_leeway(config.verifier().leeway());
+
+        Ok(
+            Self::with_client_secret_manager(http_client, config.manager().clone(), client_id)
+                .with_id_token_verifier(id_token_verifier),
+        )
+    }
+
+    pub fn with_token_url(mut self, url: impl AsRef<str>) -> Self {
+        self.token_url = url.as_ref().into();
+        self
+    }
+
+    pub fn with_revoke_url(mut self, url: impl AsRef<str>) -> Self {
+        self.revoke_url = url.as_ref().into();
+        self
+    }
+
+    pub fn with_user_migration_info_url(mut self, url: impl AsRef<str>) -> Self {
+        self.user_migration_info_url = url.as_ref().into();
+        self
+    }
+
+    /// Defaults to accepting only `client_id` as `aud`, without leeway.
+    pub fn with_id_token_verifier(mut self, verifier: IdTokenVerifier) -> Self {
+        self.id_token_verifier = verifier;
+        self
+    }
+
+    pub fn client_id(&self) -> &str {
+        &self.client_id
+    }
+
+    pub fn client_secret_manager(&self) -> &Arc<ClientSecretManager> {
+        &self.client_secret_manager
+    }
+
+    /// The cached client secret, re-minted first when it is missing or about to expire.
+    pub fn client_secret(&self) -> Result<Box<str>, CreateError> {
+        self.client_secret_manager.get_value(&self.client_id)
+    }
+}
+
+impl<C> Client<C>
+where
+    C: HttpClient + Send + Sync,
+{
+    /// [Doc](https://developer.apple.com/documentation/sign_in_with_apple/generate_and_validate_tokens)
+    pub async fn exchange_code(
+        &self,
+        code: impl AsRef<str>,
+        redirect_uri: impl Into<Option<&str>>,
+    ) -> Result<TokenResponseBody, ClientError> {
+        let grant = Grant::AuthorizationCode {
+            code: code.as_ref().into(),
+            redirect_uri: redirect_uri.into().map(Into::into),
+        };
+
+        self.respond_token(grant).await
+    }
+
+    /// [Doc](https://developer.apple.com/documentation/sign_in_with_apple/generate_and_validate_tokens)
+    pub async fn refresh_token(
+        &self,
+        refresh_token: impl AsRef<str>,
+    ) -> Result<TokenResponseBody, ClientError> {
+        let grant = Grant::RefreshToken {
+            refresh_token: refresh_token.as_ref().into(),
+        };
+
+        self.respond_token(grant).await
+    }
+
+    /// Apple recommends calling this at most once a day to check the session is still valid.
+    pub async fn validate_refresh_token(
+        &self,
+        refresh_token: impl AsRef<str>,
+        jwk_set: &JwkSet,
+    ) -> Result<IdTokenClaims, RefreshTokenError> {
+        let body = self
+            .refresh_token(refresh_token)
+            .await
+            .map_err(RefreshTokenError::RespondFailed)?;
+        let id_token = body.id_token.ok_or(RefreshTokenError::IdTokenMissing)?;
+
+        self.id_token_verifier
+            .verify(id_token, jwk_set, None)
+            .map_err(RefreshTokenError::IdTokenInvalid)
+    }
+
+    /// [Doc](https://developer.apple.com/documentation/sign_in_with_apple/revoke_tokens)
+    ///
+    /// Required when the user deletes their account.
+    pub async fn revoke_token(
+        &self,
+        token: impl AsRef<str>,
+        token_type_hint: TokenTypeHint,
+    ) -> Result<(), ClientError> {
+        let client_secret = self
+            .client_secret()
+            .map_err(ClientError::ClientSecretCreateFailed)?;
+
+        let endpoint = RevokeEndpoint::new(&self.client_id, client_secret, token, token_type_hint)
+            .with_url(&self.revoke_url);
+
+        self.http_client
+            .respond_endpoint(&endpoint)
+            .await
+            .map_err(ClientError::from)?
+            .map_err(ClientError::OAuth)
+    }
+
+    /// The `client_credentials` access token for the team transfer calls, valid for `expires_in`
+    /// seconds, so it can be reused across users.
+    pub async fn user_migration_access_token(&self) -> Result<TokenResponseBody, ClientError> {
+        let grant = Grant::ClientCredentials {
+            scope: USER_MIGRATION_SCOPE.into(),
+        };
+
+        self.respond_token(grant).await
+    }
+
+    /// Step one of a team transfer, as the old team: the `transfer_sub` for `sub`.
+    ///
+    /// `target` is the team id of the new team.
+    pub async fn transfer_sub(
+        &self,
+        access_token: impl AsRef<str>,
+        sub: impl AsRef<str>,
+        target: impl AsRef<str>,
+    ) -> Result<Box<str>, ClientError> {
+        let request = UserMigrationInfoRequest::TransferSub {
+            sub: sub.as_ref().into(),
+            target: target.as_ref().into(),
+        };
+
+        self.respond_user_migration_info(access_token.as_ref(), request)
+            .await?
+            .transfer_sub
+            .ok_or(ClientError::ResponseFieldMissing("transfer_sub"))
+    }
+
+    /// Step two of a team transfer, as the new team: the new `sub` and email for `transfer_sub`.
+    pub async fn exchange_transfer_sub(
+        &self,
+        access_token: impl AsRef<str>,
+        transfer_sub: impl AsRef<str>,
+    ) -> Result<UserMigrationInfoResponseBody, ClientError> {
+        let request = UserMigrationInfoRequest::Sub {
+            transfer_sub: transfer_sub.as_ref().into(),
+        };
+
+        let body = self
+            .respond_user_migration_info(access_token.as_ref(), request)
+            .await?;
+        if body.sub.is_none() {
+            return Err(ClientError::ResponseFieldMissing("sub"));
+        }
+
+        Ok(body)
+    }
+
+    async fn respond_user_migration_info(
+        &self,
+        access_token: &str,
+        request: UserMigrationInfoRequest,
+    ) -> Result<UserMigrationInfoResponseBody, ClientError> {
+        let client_secret = self
+            .client_secret()
+            .map_err(ClientError::ClientSecretCreateFailed)?;
+
+        let endpoint =
+            UserMigrationInfoEndpoint::new(access_token, &self.client_id, client_secret, request)
+                .with_url(&self.user_migration_info_url);
+
+        self.http_client
+            .respond_endpoint(&endpoint)
+            .await
+            .map_err(ClientError::from)?
+            .map_err(ClientError::OAuth)
+    }
+
+    async fn respond_token(&self, grant: Grant) -> Result<TokenResponseBody, ClientError> {
+        let client_secret = self
+            .client_secret()
+            .map_err(ClientError::ClientSecretCreateFailed)?;
+
+        let endpoint =
+            TokenEndpoint::new(&self.client_id, client_secret, grant).with_url(&self.token_url);
+
+        self.http_client
+            .respond_endpoint(&endpoint)
+            .await
+            .map_err(ClientError::from)?
+            .map_err(ClientError::OAuth)
+    }
+}
+
+//
+#[derive(Debug)]
+pub enum ClientError {
+    ClientSecretCreateFailed(CreateError),
+    RespondFailed(Box<dyn std::error::Error + Send + Sync>),
+    EndpointRenderRequestFailed(EndpointError),
+    EndpointParseResponseFailed(EndpointError),
+    OAuth(OAuthError),
+    ResponseFieldMissing(&'static str),
+}
+impl core::fmt::Display for ClientError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+impl std::error::Error for ClientError {}
+
+impl ClientError {
+    /// The code or refresh token is expired or revoked.
+    pub fn is_invalid_grant(&self) -> bool {
+        matches!(self, Self::OAuth(err) if err.error == OAuthErrorCode::InvalidGrant)
+    }
+
+    /// Network failures, 429 and 5xx, which are worth retrying later.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::RespondFailed(_) => true,
+            Self::EndpointParseResponseFailed(EndpointError::StatusUnexpected(status)) => {
+                status.is_server_error() || status.as_u16() == 429
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<RE> From<ClientRespondEndpointError<RE, EndpointError, EndpointError>> for ClientError
+where
+    RE: std::error::Error + Send + Sync + 'static,
+{
+    fn from(err: ClientRespondEndpointError<RE, EndpointError, EndpointError>) -> Self {
+        match err {
+            ClientRespondEndpointError::RespondFailed(err) => Self::RespondFailed(Box::new(err)),
+            ClientRespondEndpointError::EndpointRenderRequestFailed(err) => {
+                Self::EndpointRenderRequestFailed(err)
+            }
+            ClientRespondEndpointError::EndpointParseResponseFailed(err) => {
+                Self::EndpointParseResponseFailed(err)
+            }
+        }
+    }
+}
+
+//
+#[derive(Debug)]
+pub enum RefreshTokenError {
+    RespondFailed(ClientError),
+    IdTokenMissing,
+    IdTokenInvalid(IdTokenError),
+}
+impl core::fmt::Display for RefreshTokenError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+impl std::error::Error for RefreshTokenError {}
+
+impl RefreshTokenError {
+    /// Apple no longer honours the refresh token, so the user should be signed out.
+    pub fn is_revoked(&self) -> bool {
+        matches!(self, Self::RespondFailed(err) if err.is_invalid_grant())
+    }
+
+    /// Keep the session and try again later.
+    pub fn is_transient(&self) -> bool {
+        matches!(self, Self::RespondFailed(err) if err.is_transient())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    use std::sync::Mutex;
+
+    use http_api_client::{async_trait, http::StatusCode, Body, Request, Response};
+
+    use crate::oauth_error::OAuthErrorCode;
+
+    pub(crate) const P8_PRIVATE_KEY: &str = include_str!("../tests/fixtures/AuthKey_XYZ9876543.p8");
+    pub(crate) const KEY_ID: &str = "XYZ9876543";
+    pub(crate) const TEAM_ID: &str = "ABCDE12345";
+
+    /// Answers the nth request with the nth response, or the last one, keeping the requests it saw.
+    #[derive(Debug)]
+    pub(crate) struct MockClient {
+        responses: Vec<(StatusCode, Box<str>)>,
+        pub(crate) requests: Mutex<Vec<Request<Body>>>,
+    }
+
+    impl MockClient {
+        pub(crate) fn new(status: StatusCode, body: impl AsRef<str>) -> Self {
+            Self::with_responses(vec![(status, body.as_ref().into())])
+        }
+
+        pub(crate) fn with_responses(responses: Vec<(StatusCode, Box<str>)>) -> Self {
+            Self {
+                responses,
+                requests: Mutex::new(vec![]),
+            }
+        }
+
+        pub(crate) fn form(&self, n: usize) -> Vec<(String, String)> {
+            let requests = self.requests.lock().unwrap();
+            form_urlencoded::parse(requests[n].body())
+                .into_owned()
+                .collect()
+        }
+    }
+
+    #[async_trait]
+    impl HttpClient for MockClient {
+        type RespondError = std::io::Error;
+
+        async fn respond(
+            &self,
+            request: Request<Body>,
+        ) -> Result<Response<Body>, Self::RespondError> {
+            let mut requests = self.requests.lock().unwrap();
+            requests.push(request);
+            let (status, body) =
+                &self.responses[(requests.len() - 1).min(self.responses.len() - 1)];
+
+            Ok(Response::builder()
+                .status(*status)
+                .body(body.as_bytes().to_vec())
+                .unwrap())
+        }
+    }
+
+    pub(crate) const JWK_SET_JSON: &str = include_str!("../tests/fixtures/apple-jwks.json");
+    // aud com.example.app, exp 2100-01-01, signed by the key in JWK_SET_JSON.
+    pub(crate) const ID_TOKEN: &str = include_str!("../tests/fixtures/apple-id-token.jwt");
+
+    pub(crate) fn generator() -> Generator {
+        Generator::new(KEY_ID, P8_PRIVATE_KEY, TEAM_ID).unwrap()
+    }
+
+    fn form_value<'a>(form: &'a [(String, String)], key: &str) -> Option<&'a str> {
+        form.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+    }
+
+    #[tokio::test]
+    async fn test_exchange_code() {
+        let client = Client::new(
+            MockClient::new(
+                StatusCode::OK,
+                r#"{"access_token":"a","token_type":"Bearer","expires_in":3600,"refresh_token":"r","id_token":"i"}"#,
+            ),
+            generator(),
+            "com.example.app",
+        )
+        .with_token_url("http://127.0.0.1:8080/auth/token");
+
+        let body = client
+            .exchange_code("CODE", "https://example.com/cb")
+            .await
+            .unwrap();
+        assert_eq!(body.access_token.as_ref(), "a");
+        assert_eq!(body.refresh_token.as_deref(), Some("r"));
+        assert_eq!(body.id_token.as_deref(), Some("i"));
+
+        client.exchange_code("CODE2", None).await.unwrap();
+
+        {
+            let requests = client.http_client.requests.lock().unwrap();
+            assert_eq!(requests.len(), 2);
+            assert_eq!(requests[0].uri(), "http://127.0.0.1:8080/auth/token");
+        }
+
+        let form_0 = client.http_client.form(0);
+        assert_eq!(form_value(&form_0, "client_id"), Some("com.example.app"));
+        assert_eq!(
+            form_value(&form_0, "grant_type"),
+            Some("authorization_code")
+        );
+        assert_eq!(form_value(&form_0, "code"), Some("CODE"));
+        assert_eq!(
+            form_value(&form_0, "redirect_uri"),
+            Some("https://example.com/cb")
+        );
+
+        let form_1 = client.http_client.form(1);
+        assert_eq!(form_value(&form_1, "redirect_uri"), None);
+        // The client secret is minted once and reused.
+        assert_eq!(
+            form_value(&form_0, "client_secret"),
+            form_value(&form_1, "client_secret")
+        );
+        let inspection =
+            apple_siwa_client_secret::inspect(form_value(&form_0, "client_secret").unwrap())
+                .unwrap();
+        assert_eq!(inspection.claims.sub, "com.example.app");
+    }
+
+    #[tokio::test]
+    async fn test_exchange_code_with_oauth_error() {
+        let client = Client::new(
+            MockClient::new(
+                StatusCode::BAD_REQUEST,
+                r#"{"error":"invalid_grant","error_description":"The code has expired or has been revoked."}"#,
+            ),
+            generator(),
+            "com.example.app",
+        );
+
+        match client.exchange_code("CODE", None).await {
+            Err(ClientError::OAuth(err)) => {
+                assert_eq!(err.error, OAuthErrorCode::InvalidGrant);
+            }
+            x => panic!("{x:?}"),
+        }
+        assert_eq!(
+            client.http_client.requests.lock().unwrap()[0].uri(),
+            TOKEN_URL
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validate_refresh_token() {
+        let jwk_set = JwkSet::from_json(JWK_SET_JSON).unwrap();
+
+        let client = Client::new(
+            MockClient::new(
+                StatusCode::OK,
+                format!(
+                    r#"{{"access_token":"a","token_type":"Bearer","expires_in":3600,"id_token":"{ID_TOKEN}"}}"#
+                ),
+            ),
+            generator(),
+            "com.example.app",
+        );
+        let claims = client
+            .validate_refresh_token("r.1.abc", &jwk_set)
+            .await
+            .unwrap();
+        assert_eq!(
+            claims.sub.as_ref(),
+            "001234.0123456789abcdef0123456789abcdef.1234"
+        );
+        let form = client.http_client.form(0);
+        assert_eq!(form_value(&form, "grant_type"), Some("refresh_token"));
+        assert_eq!(form_value(&form, "refresh_token"), Some("r.1.abc"));
+
+        let client = Client::new(
+            MockClient::new(
+                StatusCode::OK,
+                r#"{"access_token":"a","token_type":"Bearer","expires_in":3600,"id_token":"x.y.z"}"#,
+            ),
+            generator(),
+            "com.example.app",
+        );
+        match client.validate_refresh_token("r.1.abc", &jwk_set).await {
+            Err(err @ RefreshTokenError::IdTokenInvalid(_)) => {
+                assert!(!err.is_revoked());
+                assert!(!err.is_transient());
+            }
+            x => panic!("{x:?}"),
+        }
+
+        let client = Client::new(
+            MockClient::new(StatusCode::BAD_REQUEST, r#"{"error":"invalid_grant"}"#),
+            generator(),
+            "com.example.app",
+        );
+        let err = client
+            .validate_refresh_token("r.1.abc", &jwk_set)
+            .await
+            .unwrap_err();
+        assert!(err.is_revoked());
+        assert!(!err.is_transient());
+
+        let client = Client::new(
+            MockClient::new(StatusCode::SERVICE_UNAVAILABLE, ""),
+            generator(),
+            "com.example.app",
+        );
+        let err = client
+            .validate_refresh_token("r.1.abc", &jwk_set)
+            .await
+            .unwrap_err();
+        assert!(!err.is_revoked());
+        assert!(err.is_transient());
+    }
+
+    #[tokio::test]
+    async fn test_revoke_token() {
+        let client = Client::new(
+            MockClient::new(StatusCode::OK, ""),
+            generator(),
+            "com.example.app",
+        )
+        .with_revoke_url("http://127.0.0.1:8080/auth/revoke");
+
+        client
+            .revoke_token("r.1.abc", TokenTypeHint::RefreshToken)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            client.http_client.requests.lock().unwrap()[0].uri(),
+            "http://127.0.0.1:8080/auth/revoke"
+        );
+        let form = client.http_client.form(0);
+        assert_eq!(form_value(&form, "token"), Some("r.1.abc"));
+        assert_eq!(form_value(&form, "token_type_hint"), Some("refresh_token"));
+        assert!(form_value(&form, "client_secret").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_with_config() {
+        let config = Config::new(generator(), ["com.example.app", "com.example.web"]);
+
+        let jwk_set = JwkSet::from_json(JWK_SET_JSON).unwrap();
+        let body = format!(
+            r#"{{"access_token":"a","token_type":"Bearer","expires_in":3600,"id_token":"{ID_TOKEN}"}}"#
+        );
+
+        let web_client = Client::with_config(
+            MockClient::new(StatusCode::OK, body.as_str()),
+            &config,
+            "com.example.web",
+        )
+        .unwrap();
+        // ID_TOKEN's aud is com.example.app, not the requesting client id.
+        match web_client.validate_refresh_token("r.1.abc", &jwk_set).await {
+            Err(RefreshTokenError::IdTokenInvalid(IdTokenError::AudienceMismatch(aud))) => {
+                assert_eq!(aud.as_ref(), "com.example.app")
+            }
+            x => panic!("{x:?}"),
+        }
+        // while the config accepts it for either
+        config.verify_id_token(ID_TOKEN, &jwk_set, None).unwrap();
+
+        let form = web_client.http_client.form(0);
+        assert_eq!(form_value(&form, "client_id"), Some("com.example.web"));
+        assert_eq!(
+            form_value(&form, "client_secret"),
+            Some(config.client_secret("com.example.web").unwrap().as_ref())
+        );
+
+        let app_client = Client::with_config(
+            MockClient::new(StatusCode::OK, body.as_str()),
+            &config,
+            "com.example.app",
+        )
+        .unwrap();
+        app_client
+            .validate_refresh_token("r.1.abc", &jwk_set)
+            .await
+            .unwrap();
+
+        match Client::with_config(
+            MockClient::new(StatusCode::OK, ""),
+            &config,
+            "com.example.mac",
+        ) {
+            Err(ConfigError::ClientIdUnknown(_)) => {}
+            x => panic!("{x:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_team_transfer() {
+        const ACCESS_TOKEN_BODY: &str =
+            r#"{"access_token":"a.migration","token_type":"Bearer","expires_in":3600}"#;
+        const MIGRATION_URL: &str = "http://127.0.0.1:8080/auth/usermigrationinfo";
+
+        let old_team = Client::new(
+            MockClient::with_responses(vec![
+                (StatusCode::OK, ACCESS_TOKEN_BODY.into()),
+                (StatusCode::OK, r#"{"transfer_sub":"t.abc"}"#.into()),
+            ]),
+            generator(),
+            "com.example.app",
+        )
+        .with_token_url("http://127.0.0.1:8080/auth/token")
+        .with_user_migration_info_url(MIGRATION_URL);
+
+        let access_token = old_team.user_migration_access_token().await.unwrap();
+        let transfer_sub = old_team
+            .transfer_sub(&access_token.access_token, "001234.abc.1234", "FGHIJ67890")
+            .await
+            .unwrap();
+        assert_eq!(transfer_sub.as_ref(), "t.abc");
+
+        let form = old_team.http_client.form(0);
+        assert_eq!(form_value(&form, "grant_type"), Some("client_credentials"));
+        assert_eq!(form_value(&form, "scope"), Some("user.migration"));
+        {
+            let requests = old_team.http_client.requests.lock().unwrap();
+            assert_eq!(requests[1].uri(), MIGRATION_URL);
+            assert_eq!(
+                requests[1].headers().get("Authorization").unwrap(),
+                "Bearer a.migration"
+            );
+        }
+        let form = old_team.http_client.form(1);
+        assert_eq!(form_value(&form, "sub"), Some("001234.abc.1234"));
+        assert_eq!(form_value(&form, "target"), Some("FGHIJ67890"));
+
+        // Same client id, after the app moved to the new team.
+        let new_generator = Generator::new("ABC1234567", P8_PRIVATE_KEY, "FGHIJ67890").unwrap();
+        let new_team = Client::new(
+            MockClient::with_responses(vec![
+                (StatusCode::OK, ACCESS_TOKEN_BODY.into()),
+                (
+                    StatusCode::OK,
+                    r#"{"sub":"000999.def.5678","email":"foo@privaterelay.appleid.com","is_private_email":true}"#.into(),
+                ),
+            ]),
+            new_generator,
+            "com.example.app",
+        )
+        .with_user_migration_info_url(MIGRATION_URL);
+
+        let access_token = new_team.user_migration_access_token().await.unwrap();
+        let info = new_team
+            .exchange_transfer_sub(&access_token.access_token, &transfer_sub)
+            .await
+            .unwrap();
+        assert_eq!(info.sub.as_deref(), Some("000999.def.5678"));
+        assert_eq!(info.is_private_email, Some(true));
+        assert_eq!(
+            form_value(&new_team.http_client.form(1), "transfer_sub"),
+            Some("t.abc")
+        );
+
+        let old_secret = apple_siwa_client_secret::inspect(
+            form_value(&old_team.http_client.form(1), "client_secret").unwrap(),
+        )
+        .unwrap();
+        let new_secret = apple_siwa_client_secret::inspect(
+            form_value(&new_team.http_client.form(1), "client_secret").unwrap(),
+        )
+        .unwrap();
+        assert_eq!(old_secret.claims.iss, TEAM_ID);
+        assert_eq!(new_secret.claims.iss, "FGHIJ67890");
+
+        let client = Client::new(
+            MockClient::new(StatusCode::OK, "{}"),
+            generator(),
+            "com.example.app",
+        );
+        match client
+            .transfer_sub("a.migration", "001234.abc.1234", "FGHIJ67890")
+            .await
+        {
+            Err(ClientError::ResponseFieldMissing("transfer_sub")) => {}
+            x => panic!("{x:?}"),
+        }
+    }
+}
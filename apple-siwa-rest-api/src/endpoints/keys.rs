@@ -0,0 +1,62 @@
+//! [Doc](https://developer.apple.com/documentation/sign_in_with_apple/fetch_apple_s_public_key_for_verifying_token_signature)
+
+use apple_siwa_client_secret::apple_jwt_core::JwkSet;
+use http_api_client_endpoint::{
+    http::{header::ACCEPT, Method},
+    Body, Endpoint, Request, Response, MIME_APPLICATION_JSON,
+};
+
+use super::EndpointError;
+
+pub use apple_siwa_client_secret::id_token::JWK_SET_URL as URL;
+
+//
+#[derive(Debug, Clone)]
+pub struct KeysEndpoint {
+    url: Box<str>,
+}
+
+impl KeysEndpoint {
+    pub fn new() -> Self {
+        Self { url: URL.into() }
+    }
+
+    pub fn with_url(mut self, url: impl AsRef<str>) -> Self {
+        self.url = url.as_ref().into();
+        self
+    }
+}
+
+impl Default for KeysEndpoint {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Endpoint for KeysEndpoint {
+    type RenderRequestError = EndpointError;
+
+    type ParseResponseOutput = JwkSet;
+    type ParseResponseError = EndpointError;
+
+    fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
+        Request::builder()
+            .method(Method::GET)
+            .uri(self.url.as_ref())
+            .header(ACCEPT, MIME_APPLICATION_JSON)
+            .body(vec![])
+            .map_err(EndpointError::MakeRequestFailed)
+    }
+
+    fn parse_response(
+        &self,
+        response: Response<Body>,
+    ) -> Result<Self::ParseResponseOutput, Self::ParseResponseError> {
+        let status = response.status();
+        if !status.is_success() {
+            return Err(EndpointError::StatusUnexpected(status));
+        }
+
+        serde_json::from_slice(response.body()).map_err(EndpointError::DeResponseBodyFailed)
+    }
+}
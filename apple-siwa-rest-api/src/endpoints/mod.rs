@@ -0,0 +1,94 @@
+use http_api_client_endpoint::{
+    http::{
+        header::{ACCEPT, CONTENT_TYPE},
+        Error as HttpError, Method, StatusCode,
+    },
+    Body, Request, Response, MIME_APPLICATION_JSON,
+};
+use serde::de::DeserializeOwned;
+
+use crate::oauth_error::OAuthError;
+
+pub mod keys;
+pub mod revoke;
+pub mod token;
+pub mod user_migration_info;
+
+pub use keys::KeysEndpoint;
+pub use revoke::{RevokeEndpoint, TokenTypeHint};
+pub use token::{Grant, TokenEndpoint, TokenResponseBody};
+pub use user_migration_info::{
+    UserMigrationInfoEndpoint, UserMigrationInfoRequest, UserMigrationInfoResponseBody,
+};
+
+pub const BASE_URL: &str = "https://appleid.apple.com";
+
+const MIME_APPLICATION_X_WWW_FORM_URLENCODED: &str = "application/x-www-form-urlencoded";
+
+//
+pub(crate) fn render_form_request(
+    url: &str,
+    pairs: &[(&str, &str)],
+) -> Result<Request<Body>, EndpointError> {
+    let body = form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(pairs)
+        .finish();
+
+    Request::builder()
+        .method(Method::POST)
+        .uri(url)
+        .header(CONTENT_TYPE, MIME_APPLICATION_X_WWW_FORM_URLENCODED)
+        .header(ACCEPT, MIME_APPLICATION_JSON)
+        .body(body.into_bytes())
+        .map_err(EndpointError::MakeRequestFailed)
+}
+
+/// Apple answers OAuth errors with 400 (or 401 for `invalid_client`) and a JSON body.
+pub(crate) fn parse_json_response<T: DeserializeOwned>(
+    response: Response<Body>,
+) -> Result<Result<T, OAuthError>, EndpointError> {
+    let status = response.status();
+    if status.is_success() {
+        return serde_json::from_slice::<T>(response.body())
+            .map(Ok)
+            .map_err(EndpointError::DeResponseBodyFailed);
+    }
+
+    parse_oauth_error(response)
+}
+
+/// For endpoints that answer success with 200 and an empty body.
+pub(crate) fn parse_empty_response(
+    response: Response<Body>,
+) -> Result<Result<(), OAuthError>, EndpointError> {
+    if response.status().is_success() {
+        return Ok(Ok(()));
+    }
+
+    parse_oauth_error(response)
+}
+
+fn parse_oauth_error<T>(response: Response<Body>) -> Result<Result<T, OAuthError>, EndpointError> {
+    let status = response.status();
+    if status.is_client_error() {
+        if let Ok(err) = serde_json::from_slice::<OAuthError>(response.body()) {
+            return Ok(Err(err));
+        }
+    }
+
+    Err(EndpointError::StatusUnexpected(status))
+}
+
+//
+#[derive(Debug)]
+pub enum EndpointError {
+    MakeRequestFailed(HttpError),
+    DeResponseBodyFailed(serde_json::Error),
+    StatusUnexpected(StatusCode),
+}
+impl core::fmt::Display for EndpointError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+impl std::error::Error for EndpointError {}
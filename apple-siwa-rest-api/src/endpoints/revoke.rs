@@ -0,0 +1,141 @@
+//! [Doc](https://developer.apple.com/documentation/sign_in_with_apple/revoke_tokens)
+
+use http_api_client_endpoint::{Body, Endpoint, Request, Response};
+
+use super::{parse_empty_response, render_form_request, EndpointError};
+use crate::oauth_error::OAuthError;
+
+pub const URL: &str = "https://appleid.apple.com/auth/revoke";
+
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenTypeHint {
+    AccessToken,
+    RefreshToken,
+}
+
+impl TokenTypeHint {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::AccessToken => "access_token",
+            Self::RefreshToken => "refresh_token",
+        }
+    }
+}
+
+impl core::fmt::Display for TokenTypeHint {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+//
+#[derive(Debug, Clone)]
+pub struct RevokeEndpoint {
+    url: Box<str>,
+    client_id: Box<str>,
+    client_secret: Box<str>,
+    token: Box<str>,
+    token_type_hint: TokenTypeHint,
+}
+
+impl RevokeEndpoint {
+    pub fn new(
+        client_id: impl AsRef<str>,
+        client_secret: impl AsRef<str>,
+        token: impl AsRef<str>,
+        token_type_hint: TokenTypeHint,
+    ) -> Self {
+        Self {
+            url: URL.into(),
+            client_id: client_id.as_ref().into(),
+            client_secret: client_secret.as_ref().into(),
+            token: token.as_ref().into(),
+            token_type_hint,
+        }
+    }
+
+    pub fn with_url(mut self, url: impl AsRef<str>) -> Self {
+        self.url = url.as_ref().into();
+        self
+    }
+}
+
+impl Endpoint for RevokeEndpoint {
+    type RenderRequestError = EndpointError;
+
+    type ParseResponseOutput = Result<(), OAuthError>;
+    type ParseResponseError = EndpointError;
+
+    fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
+        render_form_request(
+            &self.url,
+            &[
+                ("client_id", &self.client_id),
+                ("client_secret", &self.client_secret),
+                ("token", &self.token),
+                ("token_type_hint", self.token_type_hint.as_str()),
+            ],
+        )
+    }
+
+    fn parse_response(
+        &self,
+        response: Response<Body>,
+    ) -> Result<Self::ParseResponseOutput, Self::ParseResponseError> {
+        parse_empty_response(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use http_api_client_endpoint::http::StatusCode;
+
+    use crate::oauth_error::OAuthErrorCode;
+
+    #[test]
+    fn test_render_request() {
+        let req = RevokeEndpoint::new(
+            "com.example.app",
+            "SECRET",
+            "r.1.abc",
+            TokenTypeHint::RefreshToken,
+        )
+        .render_request()
+        .unwrap();
+        assert_eq!(req.method(), "POST");
+        assert_eq!(req.uri(), URL);
+        assert_eq!(
+            req.body(),
+            b"client_id=com.example.app&client_secret=SECRET&token=r.1.abc&token_type_hint=refresh_token"
+        );
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let endpoint = RevokeEndpoint::new(
+            "com.example.app",
+            "SECRET",
+            "a.1.abc",
+            TokenTypeHint::AccessToken,
+        );
+
+        endpoint
+            .parse_response(Response::builder().body(vec![]).unwrap())
+            .unwrap()
+            .unwrap();
+
+        let err = endpoint
+            .parse_response(
+                Response::builder()
+                    .status(StatusCode::BAD_REQUEST)
+                    .body(br#"{"error":"invalid_client"}"#.to_vec())
+                    .unwrap(),
+            )
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(err.error, OAuthErrorCode::InvalidClient);
+    }
+}
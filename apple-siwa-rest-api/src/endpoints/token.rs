@@ -0,0 +1,195 @@
+//! [Doc](https://developer.apple.com/documentation/sign_in_with_apple/generate_and_validate_tokens)
+
+use http_api_client_endpoint::{Body, Endpoint, Request, Response};
+use serde::{Deserialize, Serialize};
+
+use super::{parse_json_response, render_form_request, EndpointError};
+use crate::oauth_error::OAuthError;
+
+pub const URL: &str = "https://appleid.apple.com/auth/token";
+
+//
+#[derive(Debug, Clone)]
+pub enum Grant {
+    AuthorizationCode {
+        code: Box<str>,
+        redirect_uri: Option<Box<str>>,
+    },
+    RefreshToken {
+        refresh_token: Box<str>,
+    },
+    /// e.g. with the `user.migration` scope.
+    ClientCredentials {
+        scope: Box<str>,
+    },
+}
+
+//
+#[derive(Debug, Clone)]
+pub struct TokenEndpoint {
+    url: Box<str>,
+    client_id: Box<str>,
+    client_secret: Box<str>,
+    grant: Grant,
+}
+
+impl TokenEndpoint {
+    pub fn new(client_id: impl AsRef<str>, client_secret: impl AsRef<str>, grant: Grant) -> Self {
+        Self {
+            url: URL.into(),
+            client_id: client_id.as_ref().into(),
+            client_secret: client_secret.as_ref().into(),
+            grant,
+        }
+    }
+
+    pub fn with_url(mut self, url: impl AsRef<str>) -> Self {
+        self.url = url.as_ref().into();
+        self
+    }
+}
+
+impl Endpoint for TokenEndpoint {
+    type RenderRequestError = EndpointError;
+
+    type ParseResponseOutput = Result<TokenResponseBody, OAuthError>;
+    type ParseResponseError = EndpointError;
+
+    fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
+        let mut pairs = vec![
+            ("client_id", self.client_id.as_ref()),
+            ("client_secret", self.client_secret.as_ref()),
+        ];
+        match &self.grant {
+            Grant::AuthorizationCode { code, redirect_uri } => {
+                pairs.push(("grant_type", "authorization_code"));
+                pairs.push(("code", code));
+                if let Some(redirect_uri) = redirect_uri {
+                    pairs.push(("redirect_uri", redirect_uri));
+                }
+            }
+            Grant::RefreshToken { refresh_token } => {
+                pairs.push(("grant_type", "refresh_token"));
+                pairs.push(("refresh_token", refresh_token));
+            }
+            Grant::ClientCredentials { scope } => {
+                pairs.push(("grant_type", "client_credentials"));
+                pairs.push(("scope", scope));
+            }
+        }
+
+        render_form_request(&self.url, &pairs)
+    }
+
+    fn parse_response(
+        &self,
+        response: Response<Body>,
+    ) -> Result<Self::ParseResponseOutput, Self::ParseResponseError> {
+        parse_json_response(response)
+    }
+}
+
+//
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TokenResponseBody {
+    pub access_token: Box<str>,
+    pub token_type: Box<str>,
+    pub expires_in: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<Box<str>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id_token: Option<Box<str>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use http_api_client_endpoint::http::StatusCode;
+
+    use crate::oauth_error::OAuthErrorCode;
+
+    #[test]
+    fn test_render_request() {
+        let req = TokenEndpoint::new(
+            "com.example.app",
+            "SECRET",
+            Grant::AuthorizationCode {
+                code: "CODE".into(),
+                redirect_uri: Some("https://example.com/cb".into()),
+            },
+        )
+        .render_request()
+        .unwrap();
+        assert_eq!(req.method(), "POST");
+        assert_eq!(req.uri(), URL);
+        assert_eq!(
+            req.headers().get("Content-Type").unwrap(),
+            "application/x-www-form-urlencoded"
+        );
+        assert_eq!(
+            req.body(),
+            b"client_id=com.example.app&client_secret=SECRET&grant_type=authorization_code&code=CODE&redirect_uri=https%3A%2F%2Fexample.com%2Fcb"
+        );
+
+        let req = TokenEndpoint::new(
+            "com.example.app",
+            "SECRET",
+            Grant::RefreshToken {
+                refresh_token: "r.1.abc".into(),
+            },
+        )
+        .with_url("http://127.0.0.1:8080/auth/token")
+        .render_request()
+        .unwrap();
+        assert_eq!(req.uri(), "http://127.0.0.1:8080/auth/token");
+        assert_eq!(
+            req.body(),
+            b"client_id=com.example.app&client_secret=SECRET&grant_type=refresh_token&refresh_token=r.1.abc"
+        );
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let endpoint = TokenEndpoint::new(
+            "com.example.app",
+            "SECRET",
+            Grant::AuthorizationCode {
+                code: "CODE".into(),
+                redirect_uri: None,
+            },
+        );
+
+        let body = endpoint
+            .parse_response(
+                Response::builder()
+                    .body(br#"{"access_token":"a","token_type":"Bearer","expires_in":3600,"refresh_token":"r","id_token":"i"}"#.to_vec())
+                    .unwrap(),
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(body.refresh_token.as_deref(), Some("r"));
+        assert_eq!(body.id_token.as_deref(), Some("i"));
+
+        let err = endpoint
+            .parse_response(
+                Response::builder()
+                    .status(StatusCode::BAD_REQUEST)
+                    .body(br#"{"error":"invalid_grant"}"#.to_vec())
+                    .unwrap(),
+            )
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(err.error, OAuthErrorCode::InvalidGrant);
+
+        match endpoint.parse_response(
+            Response::builder()
+                .status(StatusCode::SERVICE_UNAVAILABLE)
+                .body(b"<html></html>".to_vec())
+                .unwrap(),
+        ) {
+            Err(EndpointError::StatusUnexpected(StatusCode::SERVICE_UNAVAILABLE)) => {}
+            x => panic!("{x:?}"),
+        }
+    }
+}
@@ -0,0 +1,165 @@
+//! [Doc](https://developer.apple.com/documentation/sign_in_with_apple/transferring_your_apps_and_users_to_another_team)
+//!
+//! Both requests need an access token from the `client_credentials` grant with the
+//! `user.migration` scope, see [`Grant::ClientCredentials`](super::Grant::ClientCredentials).
+
+use apple_siwa_client_secret::id_token::option_bool_or_string;
+use http_api_client_endpoint::{
+    http::{header::AUTHORIZATION, HeaderValue},
+    Body, Endpoint, Request, Response,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{parse_json_response, render_form_request, EndpointError};
+use crate::oauth_error::OAuthError;
+
+pub const URL: &str = "https://appleid.apple.com/auth/usermigrationinfo";
+pub const SCOPE: &str = "user.migration";
+
+//
+#[derive(Debug, Clone)]
+pub enum UserMigrationInfoRequest {
+    /// Sent with the old team's client secret, before the transfer.
+    TransferSub { sub: Box<str>, target: Box<str> },
+    /// Sent with the new team's client secret, after the transfer.
+    Sub { transfer_sub: Box<str> },
+}
+
+//
+#[derive(Debug, Clone)]
+pub struct UserMigrationInfoEndpoint {
+    url: Box<str>,
+    access_token: Box<str>,
+    client_id: Box<str>,
+    client_secret: Box<str>,
+    request: UserMigrationInfoRequest,
+}
+
+impl UserMigrationInfoEndpoint {
+    pub fn new(
+        access_token: impl AsRef<str>,
+        client_id: impl AsRef<str>,
+        client_secret: impl AsRef<str>,
+        request: UserMigrationInfoRequest,
+    ) -> Self {
+        Self {
+            url: URL.into(),
+            access_token: access_token.as_ref().into(),
+            client_id: client_id.as_ref().into(),
+            client_secret: client_secret.as_ref().into(),
+            request,
+        }
+    }
+
+    pub fn with_url(mut self, url: impl AsRef<str>) -> Self {
+        self.url = url.as_ref().into();
+        self
+    }
+}
+
+impl Endpoint for UserMigrationInfoEndpoint {
+    type RenderRequestError = EndpointError;
+
+    type ParseResponseOutput = Result<UserMigrationInfoResponseBody, OAuthError>;
+    type ParseResponseError = EndpointError;
+
+    fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
+        let mut pairs = vec![];
+        match &self.request {
+            UserMigrationInfoRequest::TransferSub { sub, target } => {
+                pairs.push(("sub", sub.as_ref()));
+                pairs.push(("target", target.as_ref()));
+            }
+            UserMigrationInfoRequest::Sub { transfer_sub } => {
+                pairs.push(("transfer_sub", transfer_sub.as_ref()));
+            }
+        }
+        pairs.push(("client_id", &self.client_id));
+        pairs.push(("client_secret", &self.client_secret));
+
+        let mut request = render_form_request(&self.url, &pairs)?;
+        let authorization = HeaderValue::from_str(&format!("Bearer {}", self.access_token))
+            .map_err(|err| EndpointError::MakeRequestFailed(err.into()))?;
+        request.headers_mut().insert(AUTHORIZATION, authorization);
+
+        Ok(request)
+    }
+
+    fn parse_response(
+        &self,
+        response: Response<Body>,
+    ) -> Result<Self::ParseResponseOutput, Self::ParseResponseError> {
+        parse_json_response(response)
+    }
+}
+
+/// `transfer_sub` for [`UserMigrationInfoRequest::TransferSub`], the rest for [`UserMigrationInfoRequest::Sub`].
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct UserMigrationInfoResponseBody {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transfer_sub: Option<Box<str>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sub: Option<Box<str>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<Box<str>>,
+    #[serde(
+        default,
+        with = "option_bool_or_string",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub is_private_email: Option<bool>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_request() {
+        let req = UserMigrationInfoEndpoint::new(
+            "ACCESS_TOKEN",
+            "com.example.app",
+            "SECRET",
+            UserMigrationInfoRequest::TransferSub {
+                sub: "001234.abc.1234".into(),
+                target: "FGHIJ67890".into(),
+            },
+        )
+        .render_request()
+        .unwrap();
+        assert_eq!(req.uri(), URL);
+        assert_eq!(
+            req.headers().get("Authorization").unwrap(),
+            "Bearer ACCESS_TOKEN"
+        );
+        assert_eq!(
+            req.body(),
+            b"sub=001234.abc.1234&target=FGHIJ67890&client_id=com.example.app&client_secret=SECRET"
+        );
+
+        let req = UserMigrationInfoEndpoint::new(
+            "ACCESS_TOKEN",
+            "com.example.app",
+            "SECRET",
+            UserMigrationInfoRequest::Sub {
+                transfer_sub: "t.abc".into(),
+            },
+        )
+        .render_request()
+        .unwrap();
+        assert_eq!(
+            req.body(),
+            b"transfer_sub=t.abc&client_id=com.example.app&client_secret=SECRET"
+        );
+    }
+
+    #[test]
+    fn test_de_response_body() {
+        let body: UserMigrationInfoResponseBody = serde_json::from_str(
+            r#"{"sub":"000999.def.5678","email":"foo@privaterelay.appleid.com","is_private_email":"true"}"#,
+        )
+        .unwrap();
+        assert_eq!(body.sub.as_deref(), Some("000999.def.5678"));
+        assert_eq!(body.is_private_email, Some(true));
+    }
+}
@@ -0,0 +1,405 @@
+//! Caches Apple's JWKS, refetching it when the TTL passes or an unknown `kid` shows up.
+
+use core::{
+    sync::atomic::{AtomicI64, Ordering},
+    time::Duration,
+};
+use std::{path::PathBuf, sync::Arc};
+
+use apple_siwa_client_secret::apple_jwt_core::{decode_unverified, DecodeError, JwkSet};
+use arc_swap::ArcSwapOption;
+use chrono::{DateTime, Utc};
+use http_api_client::{async_trait, Client as HttpClient};
+use serde::de::IgnoredAny;
+
+use crate::endpoints::keys::KeysEndpoint;
+
+//
+pub const TTL_DEFAULT: Duration = Duration::from_secs(60 * 60 * 24);
+pub const REFETCH_MIN_INTERVAL_DEFAULT: Duration = Duration::from_secs(60 * 5);
+
+//
+pub type FetchError = Box<dyn std::error::Error + Send + Sync>;
+
+#[async_trait]
+pub trait JwkSetFetcher {
+    async fn fetch(&self) -> Result<JwkSet, FetchError>;
+}
+
+/// Fetches `https://appleid.apple.com/auth/keys`, or another URL.
+#[derive(Debug, Clone)]
+pub struct HttpJwkSetFetcher<C> {
+    http_client: C,
+    endpoint: KeysEndpoint,
+}
+
+impl<C> HttpJwkSetFetcher<C> {
+    pub fn new(http_client: C) -> Self {
+        Self {
+            http_client,
+            endpoint: KeysEndpoint::new(),
+        }
+    }
+
+    pub fn with_url(mut self, url: impl AsRef<str>) -> Self {
+        self.endpoint = self.endpoint.with_url(url);
+        self
+    }
+}
+
+#[async_trait]
+impl<C> JwkSetFetcher for HttpJwkSetFetcher<C>
+where
+    C: HttpClient + Send + Sync,
+{
+    async fn fetch(&self) -> Result<JwkSet, FetchError> {
+        self.http_client
+            .respond_endpoint(&self.endpoint)
+            .await
+            .map_err(Into::into)
+    }
+}
+
+/// Reads the JWKS from a local file, e.g. in tests.
+#[derive(Debug, Clone)]
+pub struct FileJwkSetFetcher(pub PathBuf);
+
+#[async_trait]
+impl JwkSetFetcher for FileJwkSetFetcher {
+    async fn fetch(&self) -> Result<JwkSet, FetchError> {
+        JwkSet::from_file(&self.0).map_err(Into::into)
+    }
+}
+
+//
+#[derive(Debug, Clone)]
+struct Entry {
+    jwk_set: Arc<JwkSet>,
+    fetched_at: DateTime<Utc>,
+}
+
+/// Fetches at most once per `refetch_min_interval`, whether for the TTL, a missing JWKS or an
+/// unknown `kid`, and failed fetches count too, so neither forged tokens nor an outage turn into
+/// a flood of fetches.
+#[derive(Debug)]
+pub struct JwkSetCache<F> {
+    fetcher: F,
+    ttl: Duration,
+    refetch_min_interval: Duration,
+    storage: ArcSwapOption<Entry>,
+    last_fetch_at_millis: AtomicI64,
+}
+
+impl<F> JwkSetCache<F> {
+    pub fn new(fetcher: F) -> Self {
+        Self {
+            fetcher,
+            ttl: TTL_DEFAULT,
+            refetch_min_interval: REFETCH_MIN_INTERVAL_DEFAULT,
+            storage: ArcSwapOption::empty(),
+            last_fetch_at_millis: AtomicI64::new(i64::MIN),
+        }
+    }
+
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    pub fn with_refetch_min_interval(mut self, dur: Duration) -> Self {
+        self.refetch_min_interval = dur;
+        self
+    }
+
+    pub fn set(&self, jwk_set: JwkSet) {
+        self.storage.store(Some(Arc::new(Entry {
+            jwk_set: Arc::new(jwk_set),
+            fetched_at: Utc::now(),
+        })));
+    }
+
+    pub fn clear(&self) {
+        self.storage.store(None);
+    }
+
+    /// The cached JWKS, even when older than the TTL.
+    pub fn cached(&self) -> Option<Arc<JwkSet>> {
+        self.storage.load_full().map(|entry| entry.jwk_set.clone())
+    }
+
+    fn fresh(&self, now: DateTime<Utc>) -> Option<Arc<JwkSet>> {
+        let ttl = chrono::Duration::from_std(self.ttl).unwrap_or(chrono::Duration::MAX);
+
+        self.storage
+            .load_full()
+            .filter(|entry| {
+                entry
+                    .fetched_at
+                    .checked_add_signed(ttl)
+                    .is_none_or(|expires_at| now < expires_at)
+            })
+            .map(|entry| entry.jwk_set.clone())
+    }
+
+    /// Claims the next fetch for this caller, unless another one happened within `refetch_min_interval`.
+    fn try_claim_refetch(&self, now: DateTime<Utc>) -> bool {
+        let now_millis = now.timestamp_millis();
+        let last = self.last_fetch_at_millis.load(Ordering::Acquire);
+        if last != i64::MIN
+            && now_millis.saturating_sub(last)
+                < i64::try_from(self.refetch_min_interval.as_millis()).unwrap_or(i64::MAX)
+        {
+            return false;
+        }
+
+        self.last_fetch_at_millis
+            .compare_exchange(last, now_millis, Ordering::AcqRel, Ordering::Acquire)
+            .is_ok()
+    }
+}
+
+impl<F> JwkSetCache<F>
+where
+    F: JwkSetFetcher + Send + Sync,
+{
+    /// The cached JWKS, fetched first when missing or older than the TTL.
+    ///
+    /// A failed or throttled fetch falls back to the stale JWKS when there is one. Without one,
+    /// a throttled fetch is [`JwkSetCacheError::FetchThrottled`], [`refresh`](Self::refresh) at
+    /// startup to avoid that.
+    pub async fn get(&self) -> Result<Arc<JwkSet>, JwkSetCacheError> {
+        let now = Utc::now();
+        if let Some(jwk_set) = self.fresh(now) {
+            return Ok(jwk_set);
+        }
+
+        if !self.try_claim_refetch(now) {
+            return self.cached().ok_or(JwkSetCacheError::FetchThrottled);
+        }
+
+        match self.fetch_and_store().await {
+            Ok(jwk_set) => Ok(jwk_set),
+            Err(err) => self.cached().ok_or(err),
+        }
+    }
+
+    /// Like [`get`](Self::get), refetching once when `kid` is not in the cached JWKS.
+    pub async fn get_for_kid(&self, kid: &str) -> Result<Arc<JwkSet>, JwkSetCacheError> {
+        let jwk_set = self.get().await?;
+        if jwk_set.find(kid).is_some() {
+            return Ok(jwk_set);
+        }
+
+        if !self.try_claim_refetch(Utc::now()) {
+            return Ok(jwk_set);
+        }
+
+        Ok(self.fetch_and_store().await.unwrap_or(jwk_set))
+    }
+
+    /// Like [`get_for_kid`](Self::get_for_kid) with the `kid` from the token header.
+    pub async fn get_for_token(&self, token: &str) -> Result<Arc<JwkSet>, JwkSetCacheError> {
+        let (header, _) =
+            decode_unverified::<IgnoredAny>(token).map_err(JwkSetCacheError::DecodeTokenFailed)?;
+
+        match header.kid {
+            Some(kid) => self.get_for_kid(&kid).await,
+            None => self.get().await,
+        }
+    }
+
+    /// Fetches now, regardless of `refetch_min_interval`.
+    pub async fn refresh(&self) -> Result<Arc<JwkSet>, JwkSetCacheError> {
+        self.last_fetch_at_millis
+            .store(Utc::now().timestamp_millis(), Ordering::Release);
+
+        self.fetch_and_store().await
+    }
+
+    async fn fetch_and_store(&self) -> Result<Arc<JwkSet>, JwkSetCacheError> {
+        let jwk_set = self
+            .fetcher
+            .fetch()
+            .await
+            .map_err(JwkSetCacheError::FetchFailed)?;
+
+        let jwk_set = Arc::new(jwk_set);
+        self.storage.store(Some(Arc::new(Entry {
+            jwk_set: jwk_set.clone(),
+            fetched_at: Utc::now(),
+        })));
+
+        Ok(jwk_set)
+    }
+}
+
+//
+#[derive(Debug)]
+pub enum JwkSetCacheError {
+    FetchFailed(FetchError),
+    FetchThrottled,
+    DecodeTokenFailed(DecodeError),
+}
+impl core::fmt::Display for JwkSetCacheError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+impl std::error::Error for JwkSetCacheError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::sync::atomic::AtomicUsize;
+    use std::sync::Mutex;
+
+    use http_api_client::http::StatusCode;
+
+    use crate::client::tests::{MockClient, ID_TOKEN, JWK_SET_JSON};
+
+    #[derive(Debug)]
+    struct MockFetcher {
+        jwk_set: Mutex<Result<JwkSet, &'static str>>,
+        count: AtomicUsize,
+    }
+
+    impl MockFetcher {
+        fn new(jwk_set: JwkSet) -> Self {
+            Self {
+                jwk_set: Mutex::new(Ok(jwk_set)),
+                count: AtomicUsize::new(0),
+            }
+        }
+
+        fn count(&self) -> usize {
+            self.count.load(Ordering::SeqCst)
+        }
+    }
+
+    #[async_trait]
+    impl JwkSetFetcher for MockFetcher {
+        async fn fetch(&self) -> Result<JwkSet, FetchError> {
+            self.count.fetch_add(1, Ordering::SeqCst);
+            self.jwk_set.lock().unwrap().clone().map_err(Into::into)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get() {
+        let jwk_set = JwkSet::from_json(JWK_SET_JSON).unwrap();
+
+        let cache = JwkSetCache::new(MockFetcher::new(jwk_set.clone()));
+        assert!(cache.cached().is_none());
+        assert_eq!(cache.get().await.unwrap().as_ref(), &jwk_set);
+        assert_eq!(cache.get().await.unwrap().as_ref(), &jwk_set);
+        assert_eq!(cache.fetcher.count(), 1);
+
+        // Stale after the TTL, but still served when the refetch fails.
+        let cache = JwkSetCache::new(MockFetcher::new(jwk_set.clone()))
+            .with_ttl(Duration::ZERO)
+            .with_refetch_min_interval(Duration::ZERO);
+        cache.get().await.unwrap();
+        *cache.fetcher.jwk_set.lock().unwrap() = Err("unavailable");
+        assert_eq!(cache.get().await.unwrap().as_ref(), &jwk_set);
+        assert_eq!(cache.fetcher.count(), 2);
+
+        cache.clear();
+        match cache.get().await {
+            Err(JwkSetCacheError::FetchFailed(_)) => {}
+            x => panic!("{x:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_with_fetch_failing() {
+        let fetcher = MockFetcher::new(JwkSet::default());
+        *fetcher.jwk_set.lock().unwrap() = Err("unavailable");
+        let cache = JwkSetCache::new(fetcher);
+
+        match cache.get().await {
+            Err(JwkSetCacheError::FetchFailed(_)) => {}
+            x => panic!("{x:?}"),
+        }
+        for _ in 0..10 {
+            match cache.get_for_token(ID_TOKEN).await {
+                Err(JwkSetCacheError::FetchThrottled) => {}
+                x => panic!("{x:?}"),
+            }
+        }
+        assert_eq!(cache.fetcher.count(), 1);
+
+        // An interval too large for i64 millis still throttles.
+        let fetcher = MockFetcher::new(JwkSet::default());
+        *fetcher.jwk_set.lock().unwrap() = Err("unavailable");
+        let cache = JwkSetCache::new(fetcher).with_refetch_min_interval(Duration::MAX);
+        assert!(cache.get().await.is_err());
+        match cache.get().await {
+            Err(JwkSetCacheError::FetchThrottled) => {}
+            x => panic!("{x:?}"),
+        }
+        assert_eq!(cache.fetcher.count(), 1);
+
+        // Stale, the failed refetch is throttled the same way.
+        let jwk_set = JwkSet::from_json(JWK_SET_JSON).unwrap();
+        let cache = JwkSetCache::new(MockFetcher::new(jwk_set.clone())).with_ttl(Duration::ZERO);
+        cache.refresh().await.unwrap();
+        *cache.fetcher.jwk_set.lock().unwrap() = Err("unavailable");
+        for _ in 0..10 {
+            assert_eq!(cache.get().await.unwrap().as_ref(), &jwk_set);
+        }
+        assert_eq!(cache.fetcher.count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_get_for_kid() {
+        let jwk_set = JwkSet::from_json(JWK_SET_JSON).unwrap();
+
+        let cache = JwkSetCache::new(MockFetcher::new(jwk_set.clone()));
+        cache.get_for_token(ID_TOKEN).await.unwrap();
+        assert_eq!(cache.fetcher.count(), 1);
+
+        // Forged kids do not cause fetches within the interval.
+        for _ in 0..10 {
+            assert!(cache
+                .get_for_kid("FORGED")
+                .await
+                .unwrap()
+                .find("FORGED")
+                .is_none());
+        }
+        assert_eq!(cache.fetcher.count(), 1);
+
+        // A rotated key is picked up with one refetch.
+        let cache = JwkSetCache::new(MockFetcher::new(JwkSet::default()))
+            .with_refetch_min_interval(Duration::ZERO);
+        cache.get().await.unwrap();
+        *cache.fetcher.jwk_set.lock().unwrap() = Ok(jwk_set);
+        assert!(cache
+            .get_for_kid("TEST_KID")
+            .await
+            .unwrap()
+            .find("TEST_KID")
+            .is_some());
+        assert_eq!(cache.fetcher.count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_http_fetcher() {
+        let fetcher = HttpJwkSetFetcher::new(MockClient::new(StatusCode::OK, JWK_SET_JSON))
+            .with_url("http://127.0.0.1:8080/auth/keys");
+        let jwk_set = fetcher.fetch().await.unwrap();
+        assert!(jwk_set.find("TEST_KID").is_some());
+        assert_eq!(
+            fetcher.http_client.requests.lock().unwrap()[0].uri(),
+            "http://127.0.0.1:8080/auth/keys"
+        );
+
+        let fetcher = FileJwkSetFetcher(
+            [env!("CARGO_MANIFEST_DIR"), "tests/fixtures/apple-jwks.json"]
+                .iter()
+                .collect(),
+        );
+        assert_eq!(fetcher.fetch().await.unwrap(), jwk_set);
+    }
+}
@@ -0,0 +1,14 @@
+//! [Doc](https://developer.apple.com/documentation/sign_in_with_apple/sign_in_with_apple_rest_api)
+
+pub use apple_siwa_client_secret;
+pub use http_api_client;
+pub use http_api_client_endpoint;
+
+pub mod client;
+pub mod endpoints;
+pub mod jwk_set_cache;
+pub mod oauth_error;
+
+pub use client::{Client, ClientError, RefreshTokenError};
+pub use jwk_set_cache::{JwkSetCache, JwkSetFetcher};
+pub use oauth_error::{OAuthError, OAuthErrorCode};
@@ -0,0 +1,98 @@
+//! [RFC 6749 5.2](https://www.rfc-editor.org/rfc/rfc6749#section-5.2)
+
+use serde::{Deserialize, Serialize};
+
+//
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct OAuthError {
+    pub error: OAuthErrorCode,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_description: Option<Box<str>>,
+}
+impl core::fmt::Display for OAuthError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+impl std::error::Error for OAuthError {}
+
+//
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(from = "Box<str>", into = "Box<str>")]
+pub enum OAuthErrorCode {
+    InvalidRequest,
+    InvalidClient,
+    InvalidGrant,
+    UnauthorizedClient,
+    UnsupportedGrantType,
+    InvalidScope,
+    Other(Box<str>),
+}
+
+impl OAuthErrorCode {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::InvalidRequest => "invalid_request",
+            Self::InvalidClient => "invalid_client",
+            Self::InvalidGrant => "invalid_grant",
+            Self::UnauthorizedClient => "unauthorized_client",
+            Self::UnsupportedGrantType => "unsupported_grant_type",
+            Self::InvalidScope => "invalid_scope",
+            Self::Other(s) => s,
+        }
+    }
+}
+
+impl From<&str> for OAuthErrorCode {
+    fn from(s: &str) -> Self {
+        match s {
+            "invalid_request" => Self::InvalidRequest,
+            "invalid_client" => Self::InvalidClient,
+            "invalid_grant" => Self::InvalidGrant,
+            "unauthorized_client" => Self::UnauthorizedClient,
+            "unsupported_grant_type" => Self::UnsupportedGrantType,
+            "invalid_scope" => Self::InvalidScope,
+            s => Self::Other(s.into()),
+        }
+    }
+}
+impl From<Box<str>> for OAuthErrorCode {
+    fn from(s: Box<str>) -> Self {
+        Self::from(s.as_ref())
+    }
+}
+impl From<OAuthErrorCode> for Box<str> {
+    fn from(x: OAuthErrorCode) -> Self {
+        match x {
+            OAuthErrorCode::Other(s) => s,
+            x => x.as_str().into(),
+        }
+    }
+}
+
+impl core::fmt::Display for OAuthErrorCode {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_de() {
+        let err: OAuthError = serde_json::from_str(r#"{"error":"invalid_grant"}"#).unwrap();
+        assert_eq!(err.error, OAuthErrorCode::InvalidGrant);
+        assert_eq!(err.error_description, None);
+
+        let err: OAuthError =
+            serde_json::from_str(r#"{"error":"foo","error_description":"bar"}"#).unwrap();
+        assert_eq!(err.error, OAuthErrorCode::Other("foo".into()));
+        assert_eq!(err.error_description.as_deref(), Some("bar"));
+        assert_eq!(
+            serde_json::to_string(&err).unwrap(),
+            r#"{"error":"foo","error_description":"bar"}"#
+        );
+    }
+}